@@ -0,0 +1,19 @@
+//! An image that returns `EFI_ABORTED` from its entry point.
+//!
+//! ## License
+//!
+//! Copyright (c) Microsoft Corporation.
+//!
+//! SPDX-License-Identifier: Apache-2.0
+//!
+#![no_std]
+#![no_main]
+
+use r_efi::efi;
+
+use test_images as _;
+
+#[unsafe(export_name = "efi_main")]
+extern "efiapi" fn efi_main(_image: efi::Handle, _st: *mut efi::SystemTable) -> efi::Status {
+    efi::Status::ABORTED
+}
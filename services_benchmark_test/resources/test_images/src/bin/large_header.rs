@@ -0,0 +1,65 @@
+//! A no-op image with 32 additional one-byte sections to enlarge the PE section table.
+//!
+//! ## License
+//!
+//! Copyright (c) Microsoft Corporation.
+//!
+//! SPDX-License-Identifier: Apache-2.0
+//!
+#![no_std]
+#![no_main]
+
+use r_efi::efi;
+
+use test_images as _;
+
+/// Places a one-byte static in its own section.
+macro_rules! extra_sections {
+    ($($name:ident => $section:literal),* $(,)?) => {
+        $(
+            #[used]
+            #[unsafe(link_section = $section)]
+            static $name: u8 = 0;
+        )*
+    };
+}
+
+extra_sections! {
+    SECTION_00 => ".xs00",
+    SECTION_01 => ".xs01",
+    SECTION_02 => ".xs02",
+    SECTION_03 => ".xs03",
+    SECTION_04 => ".xs04",
+    SECTION_05 => ".xs05",
+    SECTION_06 => ".xs06",
+    SECTION_07 => ".xs07",
+    SECTION_08 => ".xs08",
+    SECTION_09 => ".xs09",
+    SECTION_10 => ".xs10",
+    SECTION_11 => ".xs11",
+    SECTION_12 => ".xs12",
+    SECTION_13 => ".xs13",
+    SECTION_14 => ".xs14",
+    SECTION_15 => ".xs15",
+    SECTION_16 => ".xs16",
+    SECTION_17 => ".xs17",
+    SECTION_18 => ".xs18",
+    SECTION_19 => ".xs19",
+    SECTION_20 => ".xs20",
+    SECTION_21 => ".xs21",
+    SECTION_22 => ".xs22",
+    SECTION_23 => ".xs23",
+    SECTION_24 => ".xs24",
+    SECTION_25 => ".xs25",
+    SECTION_26 => ".xs26",
+    SECTION_27 => ".xs27",
+    SECTION_28 => ".xs28",
+    SECTION_29 => ".xs29",
+    SECTION_30 => ".xs30",
+    SECTION_31 => ".xs31",
+}
+
+#[unsafe(export_name = "efi_main")]
+extern "efiapi" fn efi_main(_image: efi::Handle, _st: *mut efi::SystemTable) -> efi::Status {
+    efi::Status::SUCCESS
+}
@@ -0,0 +1,26 @@
+//! An image that calls `AllocatePool` and `FreePool` once before exiting.
+//!
+//! ## License
+//!
+//! Copyright (c) Microsoft Corporation.
+//!
+//! SPDX-License-Identifier: Apache-2.0
+//!
+#![no_std]
+#![no_main]
+
+use r_efi::efi;
+
+use test_images as _;
+
+#[unsafe(export_name = "efi_main")]
+extern "efiapi" fn efi_main(image: efi::Handle, st: *mut efi::SystemTable) -> efi::Status {
+    // SAFETY: The firmware passes a valid system table with valid boot services to the entry point.
+    let bs = unsafe { &*(*st).boot_services };
+    let mut buffer = core::ptr::null_mut();
+    let mut status = (bs.allocate_pool)(efi::BOOT_SERVICES_DATA, 64, &mut buffer);
+    if !status.is_error() {
+        status = (bs.free_pool)(buffer);
+    }
+    (bs.exit)(image, status, 0, core::ptr::null_mut())
+}
@@ -0,0 +1,33 @@
+//! A no-op image with a `.data` and a `.bss` section in addition to its code.
+//!
+//! ## License
+//!
+//! Copyright (c) Microsoft Corporation.
+//!
+//! SPDX-License-Identifier: Apache-2.0
+//!
+#![no_std]
+#![no_main]
+
+use core::ptr::{addr_of_mut, read_volatile, write_volatile};
+
+use r_efi::efi;
+
+use test_images as _;
+
+/// Initialized, writable data placed in `.data`.
+static mut DATA: [u32; 256] = [0x5a5a_5a5a; 256];
+
+/// Zero-initialized, writable data placed in `.bss`.
+static mut BSS: [u8; 16384] = [0; 16384];
+
+#[unsafe(export_name = "efi_main")]
+extern "efiapi" fn efi_main(_image: efi::Handle, _st: *mut efi::SystemTable) -> efi::Status {
+    // Touch both sections so neither is optimized away.
+    // SAFETY: The image runs single-threaded and both statics are only accessed here.
+    unsafe {
+        let value = read_volatile(addr_of_mut!(DATA) as *const u32);
+        write_volatile(addr_of_mut!(BSS) as *mut u8, value as u8);
+    }
+    efi::Status::SUCCESS
+}
@@ -0,0 +1,24 @@
+//! Support code shared by the test images.
+//!
+//! ## License
+//!
+//! Copyright (c) Microsoft Corporation.
+//!
+//! SPDX-License-Identifier: Apache-2.0
+//!
+#![no_std]
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+/// Target of `RELOCATION_ANCHOR`.
+static RELOCATION_TARGET: u8 = 0;
+
+/// An absolute address, which needs a base relocation.
+///
+/// The images are otherwise position independent, so the linker would not emit a `.reloc` section and the images could
+/// only be loaded at their preferred base address. Keeping this pointer makes every image relocatable.
+#[used]
+pub static RELOCATION_ANCHOR: &u8 = &RELOCATION_TARGET;
@@ -5,9 +5,18 @@
 //! SPDX-License-Identifier: Apache-2.0
 //!
 
-use patina::uefi_protocol::ProtocolInterface;
+use crate::alloc::boxed::Box;
+
+use mu_rust_helpers::perf_timer::{Arch, ArchFunctionality as _};
+use patina::{
+    boot_services::{BootServices as _, c_ptr::PtrMetadata, tpl::Tpl},
+    uefi_protocol::ProtocolInterface,
+};
 use r_efi::efi;
 
+use crate::{BOOT_SERVICES, error::BenchError, measure::Measurements};
+
+pub(crate) mod baseline;
 pub(crate) mod controller;
 pub(crate) mod event;
 pub(crate) mod image;
@@ -16,11 +25,80 @@ pub(crate) mod misc;
 pub(crate) mod protocol;
 pub(crate) mod tpl;
 
+/// A handle along with the key needed to uninstall the protocol installed on it.
+type Install<T> = (efi::Handle, PtrMetadata<'static, Box<T>>);
+
+/// The highest task priority level, at which interrupts are disabled.
+const TPL_HIGH_LEVEL: Tpl = Tpl(31);
+
 /// Some static test guids for protocols.
 const TEST_GUID1: efi::Guid =
     efi::Guid::from_fields(0x12345678, 0x1234, 0x5678, 0x9a, 0xbc, &[0xde, 0xf0, 0x12, 0x34, 0x56, 0x78]);
 const TEST_GUID2: efi::Guid =
     efi::Guid::from_fields(0x87654321, 0x4321, 0x8765, 0xba, 0x98, &[0x76, 0x54, 0x32, 0x10, 0xfe, 0xdc]);
+const TEST_GUID3: efi::Guid =
+    efi::Guid::from_fields(0x2b7c4e91, 0x5d3a, 0x4f08, 0x9e, 0x61, &[0xa4, 0x0d, 0x73, 0xc8, 0x1f, 0x5b]);
+
+/// Number of GUIDs in [`MANY_GUIDS`].
+const MANY_GUID_COUNT: usize = 100;
+
+/// Distinct GUIDs for installing many protocols with null interfaces on a single handle.
+static MANY_GUIDS: [efi::Guid; MANY_GUID_COUNT] = {
+    let mut guids = [TEST_GUID1; MANY_GUID_COUNT];
+    let mut i = 0;
+    while i < MANY_GUID_COUNT {
+        guids[i] = efi::Guid::from_fields(
+            0x6d4e0000 | i as u32,
+            0x9c1f,
+            0x4b7a,
+            0x81,
+            0x3e,
+            &[0x52, 0xa9, 0x0c, 0x77, 0xd4, 0x18],
+        );
+        i += 1;
+    }
+    guids
+};
+
+/// Repeats `op` for a window of `window_ms` milliseconds and records the achieved throughput.
+/// The mean cycles per operation are recorded as the main result and the operations per millisecond as the throughput,
+/// described by `label`.
+fn record_window_throughput(
+    stats: &mut Measurements,
+    label: &'static str,
+    window_ms: u64,
+    mut op: impl FnMut() -> Result<(), BenchError>,
+) -> Result<(), BenchError> {
+    let cycles_per_ms = Arch::perf_frequency() / 1000;
+    let budget = cycles_per_ms * window_ms;
+
+    let mut ops: u64 = 0;
+    let start = Arch::cpu_count();
+    let mut now = start;
+    while now - start < budget {
+        op()?;
+        ops += 1;
+        now = Arch::cpu_count();
+    }
+    let elapsed = now - start;
+
+    stats.update(elapsed as f64 / ops.max(1) as f64);
+    stats.update_throughput(label, ops as f64 * cycles_per_ms as f64 / elapsed.max(1) as f64);
+    Ok(())
+}
+
+/// Returns the firmware's raw boot services table, found through the system table referenced by the loaded image
+/// protocol of `image_handle`. This is needed for calls that the `BootServices` wrapper does not expose.
+fn efi_boot_services_table(image_handle: efi::Handle) -> Result<&'static efi::BootServices, BenchError> {
+    // SAFETY: There is only one reference to the `loaded_image_protocol` interface.
+    let loaded_image_protocol = unsafe {
+        BOOT_SERVICES
+            .handle_protocol::<efi::protocols::loaded_image::Protocol>(image_handle)
+            .map_err(|e| BenchError::setup("Failed to get loaded image protocol", e))?
+    };
+    // SAFETY: The system table and its boot services table stay valid while boot services are available.
+    Ok(unsafe { &*(*loaded_image_protocol.system_table).boot_services })
+}
 
 pub struct TestProtocol1 {}
 
@@ -35,3 +113,74 @@ pub struct TestProtocol2 {}
 unsafe impl ProtocolInterface for TestProtocol2 {
     const PROTOCOL_GUID: efi::Guid = TEST_GUID2;
 }
+
+/// A test protocol with a non-empty interface, so that installed interface pointers are distinct and non-null.
+pub struct TestDataProtocol {
+    _value: u64,
+}
+
+// SAFETY: This is a test protocol with no layout requirements.
+unsafe impl ProtocolInterface for TestDataProtocol {
+    const PROTOCOL_GUID: efi::Guid = TEST_GUID3;
+}
+
+/// A hardware PCI device path node (`Pci(Device, Function)`).
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub(crate) struct PciDevicePathNode {
+    header: efi::protocols::device_path::Protocol,
+    function: u8,
+    device: u8,
+}
+
+impl PciDevicePathNode {
+    pub(crate) const fn new(device: u8, function: u8) -> Self {
+        Self {
+            header: efi::protocols::device_path::Protocol {
+                r#type: efi::protocols::device_path::TYPE_HARDWARE,
+                sub_type: efi::protocols::device_path::Hardware::SUBTYPE_PCI,
+                length: [size_of::<Self>() as u8, 0],
+            },
+            function,
+            device,
+        }
+    }
+}
+
+/// An ACPI device path node (`Acpi(HID, UID)`).
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub(crate) struct AcpiDevicePathNode {
+    header: efi::protocols::device_path::Protocol,
+    hid: u32,
+    uid: u32,
+}
+
+impl AcpiDevicePathNode {
+    /// The compressed EISA ID of a PCI root bridge (`PNP0A03`).
+    const PCI_ROOT_HID: u32 = 0x0A03_41D0;
+    /// The ACPI device path sub-type.
+    const SUBTYPE_ACPI: u8 = 0x01;
+
+    /// Creates a `PciRoot(uid)` node.
+    pub(crate) const fn pci_root(uid: u32) -> Self {
+        Self {
+            header: efi::protocols::device_path::Protocol {
+                r#type: efi::protocols::device_path::TYPE_ACPI,
+                sub_type: Self::SUBTYPE_ACPI,
+                length: [size_of::<Self>() as u8, 0],
+            },
+            hid: Self::PCI_ROOT_HID,
+            uid,
+        }
+    }
+}
+
+/// The node that terminates an entire device path.
+pub(crate) const END_DEVICE_PATH_NODE: efi::protocols::device_path::End = efi::protocols::device_path::End {
+    header: efi::protocols::device_path::Protocol {
+        r#type: efi::protocols::device_path::TYPE_END,
+        sub_type: efi::protocols::device_path::End::SUBTYPE_ENTIRE,
+        length: [size_of::<efi::protocols::device_path::End>() as u8, 0],
+    },
+};
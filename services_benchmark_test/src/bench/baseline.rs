@@ -0,0 +1,36 @@
+//! Benchmarks for the overhead of the boot services wrapper itself, independent of the firmware.
+//!
+//! Copyright (c) Microsoft Corporation.
+//!
+//! SPDX-License-Identifier: Apache-2.0
+//!
+
+use mu_rust_helpers::perf_timer::{Arch, ArchFunctionality as _};
+use patina::boot_services::StandardBootServices;
+use r_efi::efi;
+
+use crate::{bench::efi_boot_services_table, config::BenchConfig, error::BenchError, measure::Measurements};
+
+/// Benchmarks initializing a fresh `StandardBootServices` wrapper with the firmware's boot services table.
+/// No firmware service is called, so this is the wrapper's own setup overhead.
+pub(crate) fn bench_boot_services_init_cost(
+    handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let efi_boot_services = efi_boot_services_table(handle)?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let boot_services = StandardBootServices::new_uninit();
+
+        let start = Arch::cpu_count();
+        boot_services.init(efi_boot_services);
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Keep the compiler from eliding the unused wrapper.
+        core::hint::black_box(&boot_services);
+    }
+    Ok(stats)
+}
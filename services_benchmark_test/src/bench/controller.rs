@@ -5,66 +5,89 @@
 //! SPDX-License-Identifier: Apache-2.0
 //!
 
-use crate::alloc::{boxed::Box, vec};
+use crate::alloc::{boxed::Box, vec, vec::Vec};
 
 use mu_rust_helpers::perf_timer::{Arch, ArchFunctionality as _};
 use patina::boot_services::BootServices;
 use r_efi::efi;
-use rolling_stats::Stats;
 
 use crate::{
     BOOT_SERVICES,
-    bench::{TestProtocol1, TestProtocol2},
+    bench::{END_DEVICE_PATH_NODE, Install, PciDevicePathNode, TEST_GUID1, TestProtocol1, TestProtocol2},
+    config::BenchConfig,
     error::BenchError,
+    measure::Measurements,
 };
 
-/// Benchmarks the UEFI driver model's controller connection mechanism.
-pub(crate) fn bench_connect_controller(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    /// Mock driver binding protocols definitions.
-    extern "efiapi" fn mock_supported(
-        _this: *mut efi::protocols::driver_binding::Protocol,
-        _controller_handle: efi::Handle,
-        _remaining_device_path: *mut efi::protocols::device_path::Protocol,
-    ) -> efi::Status {
-        efi::Status::SUCCESS
-    }
+/// Handles for a mock controller and the mock driver that manages it.
+struct MockDriverSetup {
+    controller_install: Install<TestProtocol1>,
+    driver_install: Install<efi::protocols::device_path::Protocol>,
+    image_install: Install<TestProtocol2>,
+    driver_binding: Install<efi::protocols::driver_binding::Protocol>,
+}
 
-    extern "efiapi" fn mock_start(
-        _this: *mut efi::protocols::driver_binding::Protocol,
-        _controller_handle: efi::Handle,
-        _remaining_device_path: *mut efi::protocols::device_path::Protocol,
-    ) -> efi::Status {
-        efi::Status::SUCCESS
-    }
+/// Mock driver binding protocols definitions.
+extern "efiapi" fn mock_supported(
+    _this: *mut efi::protocols::driver_binding::Protocol,
+    _controller_handle: efi::Handle,
+    _remaining_device_path: *mut efi::protocols::device_path::Protocol,
+) -> efi::Status {
+    efi::Status::SUCCESS
+}
 
-    extern "efiapi" fn mock_stop(
-        _this: *mut efi::protocols::driver_binding::Protocol,
-        _controller_handle: efi::Handle,
-        _num_children: usize,
-        _child_handle_buffer: *mut efi::Handle,
-    ) -> efi::Status {
-        efi::Status::SUCCESS
-    }
+extern "efiapi" fn mock_supported_unsupported(
+    _this: *mut efi::protocols::driver_binding::Protocol,
+    _controller_handle: efi::Handle,
+    _remaining_device_path: *mut efi::protocols::device_path::Protocol,
+) -> efi::Status {
+    efi::Status::UNSUPPORTED
+}
 
-    // Setup controller, driver, and image handles with test protocols.
+extern "efiapi" fn mock_start(
+    _this: *mut efi::protocols::driver_binding::Protocol,
+    _controller_handle: efi::Handle,
+    _remaining_device_path: *mut efi::protocols::device_path::Protocol,
+) -> efi::Status {
+    efi::Status::SUCCESS
+}
+
+extern "efiapi" fn mock_stop(
+    _this: *mut efi::protocols::driver_binding::Protocol,
+    _controller_handle: efi::Handle,
+    _num_children: usize,
+    _child_handle_buffer: *mut efi::Handle,
+) -> efi::Status {
+    efi::Status::SUCCESS
+}
+
+/// Setup controller, driver, and image handles with test protocols and install the mock driver binding.
+fn install_mock_driver() -> Result<MockDriverSetup, BenchError> {
+    install_mock_driver_with_supported(mock_supported)
+}
+
+/// Same as [`install_mock_driver`], but with the given `Supported` function in the driver binding.
+fn install_mock_driver_with_supported(
+    supported: efi::protocols::driver_binding::ProtocolSupported,
+) -> Result<MockDriverSetup, BenchError> {
     let controller_install = BOOT_SERVICES
         .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-        .map_err(|e| BenchError::BenchSetup("Failed to install protocol interface for controller", e))?;
+        .map_err(|e| BenchError::setup("Failed to install protocol interface for controller", e))?;
 
     let driver_install = BOOT_SERVICES
         .install_protocol_interface(
             None,
             Box::new(efi::protocols::device_path::Protocol { r#type: 4, sub_type: 5, length: [0, 0] }),
         )
-        .map_err(|e| BenchError::BenchSetup("Failed to install protocol interface for driver", e))?;
+        .map_err(|e| BenchError::setup("Failed to install protocol interface for driver", e))?;
 
     let image_install = BOOT_SERVICES
         .install_protocol_interface(None, Box::new(TestProtocol2 {}))
-        .map_err(|e| BenchError::BenchSetup("Failed to install protocol interface for image", e))?;
+        .map_err(|e| BenchError::setup("Failed to install protocol interface for image", e))?;
 
     let binding = Box::new(efi::protocols::driver_binding::Protocol {
         version: 10,
-        supported: mock_supported,
+        supported,
         start: mock_start,
         stop: mock_stop,
         driver_binding_handle: driver_install.0,
@@ -73,37 +96,275 @@ pub(crate) fn bench_connect_controller(_handle: efi::Handle, num_calls: usize) -
 
     let driver_binding = BOOT_SERVICES
         .install_protocol_interface(Some(driver_install.0), binding)
-        .map_err(|e| BenchError::BenchSetup("Failed to install protocol interface for driver binding", e))?;
+        .map_err(|e| BenchError::setup("Failed to install protocol interface for driver binding", e))?;
+
+    Ok(MockDriverSetup { controller_install, driver_install, image_install, driver_binding })
+}
+
+/// Uninstall protocols to prevent side effects.
+fn uninstall_mock_driver(setup: MockDriverSetup) -> Result<(), BenchError> {
+    BOOT_SERVICES
+        .uninstall_protocol_interface(setup.driver_binding.0, setup.driver_binding.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol interface", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(setup.driver_install.0, setup.driver_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol interface", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(setup.image_install.0, setup.image_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol interface", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(setup.controller_install.0, setup.controller_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol interface", e))?;
+    Ok(())
+}
+
+/// Benchmarks the UEFI driver model's controller connection mechanism.
+/// The driver list holds only the mock driver, so the firmware only has to probe the listed driver.
+pub(crate) fn bench_connect_controller(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    connect_mock_controller(num_calls, true, config)
+}
+
+/// Benchmarks controller connection without a driver list, so the firmware tries every driver in the system.
+pub(crate) fn bench_connect_controller_wildcard(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    connect_mock_controller(num_calls, false, config)
+}
+
+/// Times connecting the mock driver to its controller, either through an explicit driver list (`targeted`) or by
+/// passing no driver list at all.
+fn connect_mock_controller(num_calls: usize, targeted: bool, config: &BenchConfig) -> Result<Measurements, BenchError> {
+    let setup = install_mock_driver()?;
 
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
+        // An empty list is passed to the firmware as a NULL driver list.
+        let driver_image_handles = if targeted { vec![setup.driver_install.0] } else { Vec::new() };
         // SAFETY: All handles and pointers are valid (constructed by benchmark).
         unsafe {
             BOOT_SERVICES
-                .connect_controller(controller_install.0, vec![driver_install.0], core::ptr::null_mut(), false)
-                .map_err(|e| BenchError::BenchTest("Failed to connect controller", e))?;
+                .connect_controller(setup.controller_install.0, driver_image_handles, core::ptr::null_mut(), false)
+                .map_err(|e| BenchError::measure("Failed to connect controller", e))?;
         }
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
         BOOT_SERVICES
-            .disconnect_controller(controller_install.0, None, None)
-            .map_err(|e| BenchError::BenchCleanup("Failed to disconnect controller", e))?;
+            .disconnect_controller(setup.controller_install.0, None, None)
+            .map_err(|e| BenchError::cleanup("Failed to disconnect controller", e))?;
+    }
+
+    uninstall_mock_driver(setup)?;
+
+    Ok(stats)
+}
+
+/// Benchmarks controller connection when the image of the mock driver also owns a second driver binding.
+/// Both drivers are listed and started on the controller, which exercises the firmware's per-image driver scan.
+pub(crate) fn bench_connect_controller_multi_image_driver(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let setup = install_mock_driver()?;
+
+    // Install a second driver binding on its own handle, owned by the same image as the first one.
+    let second_driver_install = BOOT_SERVICES
+        .install_protocol_interface(
+            None,
+            Box::new(efi::protocols::device_path::Protocol { r#type: 4, sub_type: 5, length: [0, 0] }),
+        )
+        .map_err(|e| BenchError::setup("Failed to install protocol interface for second driver", e))?;
+    let second_binding = Box::new(efi::protocols::driver_binding::Protocol {
+        version: 10,
+        supported: mock_supported,
+        start: mock_start,
+        stop: mock_stop,
+        driver_binding_handle: second_driver_install.0,
+        image_handle: setup.image_install.0,
+    });
+    let second_driver_binding = BOOT_SERVICES
+        .install_protocol_interface(Some(second_driver_install.0), second_binding)
+        .map_err(|e| BenchError::setup("Failed to install protocol interface for second driver binding", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: All handles and pointers are valid (constructed by benchmark).
+        unsafe {
+            BOOT_SERVICES
+                .connect_controller(
+                    setup.controller_install.0,
+                    vec![setup.driver_install.0, second_driver_install.0],
+                    core::ptr::null_mut(),
+                    false,
+                )
+                .map_err(|e| BenchError::measure("Failed to connect controller", e))?;
+        }
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+        BOOT_SERVICES
+            .disconnect_controller(setup.controller_install.0, None, None)
+            .map_err(|e| BenchError::cleanup("Failed to disconnect controller", e))?;
     }
 
-    // Uninstall protocols to prevent side effects.
-    BOOT_SERVICES
-        .uninstall_protocol_interface(driver_binding.0, driver_binding.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall protocol interface", e))?;
-    BOOT_SERVICES
-        .uninstall_protocol_interface(driver_install.0, driver_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall protocol interface", e))?;
     BOOT_SERVICES
-        .uninstall_protocol_interface(image_install.0, image_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall protocol interface", e))?;
+        .uninstall_protocol_interface(second_driver_binding.0, second_driver_binding.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol interface", e))?;
     BOOT_SERVICES
-        .uninstall_protocol_interface(controller_install.0, controller_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall protocol interface", e))?;
+        .uninstall_protocol_interface(second_driver_install.0, second_driver_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol interface", e))?;
+    uninstall_mock_driver(setup)?;
+
+    Ok(stats)
+}
+
+/// Benchmarks controller connection when the only driver rejects the controller in `Supported`.
+/// This "probe and reject" path is the common case during `ConnectAllDriversToAllControllers`.
+pub(crate) fn bench_connect_controller_driver_unsupported(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let setup = install_mock_driver_with_supported(mock_supported_unsupported)?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: All handles and pointers are valid (constructed by benchmark).
+        let result = unsafe {
+            BOOT_SERVICES.connect_controller(
+                setup.controller_install.0,
+                vec![setup.driver_install.0],
+                core::ptr::null_mut(),
+                false,
+            )
+        };
+        let end = Arch::cpu_count();
+
+        // No driver starts on the controller, so the connection is expected to fail.
+        match result {
+            Err(efi::Status::NOT_FOUND) => stats.update((end - start) as f64),
+            Err(e) => return Err(BenchError::measure("Connect controller returned unexpected status", e)),
+            Ok(()) => {
+                return Err(BenchError::measure("Connect controller unexpectedly succeeded", efi::Status::SUCCESS));
+            }
+        }
+    }
+
+    uninstall_mock_driver(setup)?;
+
+    Ok(stats)
+}
+
+/// Benchmarks controller connection with a remaining device path.
+/// The remaining device path restricts which child the driver is asked to `Start`.
+pub(crate) fn bench_connect_controller_with_remaining_path(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    /// A single-node device path (`Pci(0x0,0x0)`).
+    #[repr(C)]
+    struct RemainingDevicePath {
+        pci: PciDevicePathNode,
+        end: efi::protocols::device_path::End,
+    }
+
+    let setup = install_mock_driver()?;
+    let mut remaining_path = RemainingDevicePath { pci: PciDevicePathNode::new(0, 0), end: END_DEVICE_PATH_NODE };
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: All handles and pointers are valid (constructed by benchmark).
+        unsafe {
+            BOOT_SERVICES
+                .connect_controller(
+                    setup.controller_install.0,
+                    vec![setup.driver_install.0],
+                    &mut remaining_path as *mut RemainingDevicePath as *mut efi::protocols::device_path::Protocol,
+                    false,
+                )
+                .map_err(|e| BenchError::measure("Failed to connect controller with remaining path", e))?;
+        }
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+        BOOT_SERVICES
+            .disconnect_controller(setup.controller_install.0, None, None)
+            .map_err(|e| BenchError::cleanup("Failed to disconnect controller", e))?;
+    }
+
+    uninstall_mock_driver(setup)?;
+
+    Ok(stats)
+}
+
+/// Benchmarks controller connection when the controller already has `N` child handles.
+/// Production controllers usually have children created by their bus driver, so each call sees a partially connected tree.
+pub(crate) fn bench_connect_controller_with_children<const N: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let setup = install_mock_driver()?;
+
+    // Create the child handles and attach them to the controller the way a bus driver would.
+    let mut child_installs = Vec::with_capacity(N);
+    for _ in 0..N {
+        let child_install = BOOT_SERVICES
+            .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+            .map_err(|e| BenchError::setup("Failed to install protocol interface for child", e))?;
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        unsafe {
+            BOOT_SERVICES
+                .open_protocol::<TestProtocol1>(
+                    setup.controller_install.0,
+                    setup.driver_install.0,
+                    child_install.0,
+                    efi::OPEN_PROTOCOL_BY_CHILD_CONTROLLER,
+                )
+                .map_err(|e| BenchError::setup("Failed to open protocol by child controller", e))?;
+        }
+        child_installs.push(child_install);
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: All handles and pointers are valid (constructed by benchmark).
+        unsafe {
+            BOOT_SERVICES
+                .connect_controller(
+                    setup.controller_install.0,
+                    vec![setup.driver_install.0],
+                    core::ptr::null_mut(),
+                    false,
+                )
+                .map_err(|e| BenchError::measure("Failed to connect controller", e))?;
+        }
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+        BOOT_SERVICES
+            .disconnect_controller(setup.controller_install.0, None, None)
+            .map_err(|e| BenchError::cleanup("Failed to disconnect controller", e))?;
+    }
+
+    for child_install in child_installs {
+        BOOT_SERVICES
+            .close_protocol(setup.controller_install.0, &TEST_GUID1, setup.driver_install.0, child_install.0)
+            .map_err(|e| BenchError::cleanup("Failed to close protocol by child controller", e))?;
+        BOOT_SERVICES
+            .uninstall_protocol_interface(child_install.0, child_install.1)
+            .map_err(|e| BenchError::cleanup("Failed to uninstall protocol interface", e))?;
+    }
+    uninstall_mock_driver(setup)?;
 
     Ok(stats)
 }
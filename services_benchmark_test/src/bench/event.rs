@@ -7,88 +7,521 @@
 
 use crate::alloc::vec::Vec;
 
-use core::{ffi::c_void, ptr};
+use core::{
+    ffi::c_void,
+    ptr,
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+};
 
 use mu_rust_helpers::perf_timer::{Arch, ArchFunctionality as _};
-use patina::boot_services::{BootServices, event::EventType, tpl::Tpl};
+use patina::{
+    base::UEFI_PAGE_SIZE,
+    boot_services::{
+        BootServices,
+        allocation::AllocType,
+        event::{EventTimerType, EventType},
+        tpl::Tpl,
+    },
+    efi_types::EfiMemoryType,
+};
 use r_efi::efi;
-use rolling_stats::Stats;
 
-use crate::{BOOT_SERVICES, error::BenchError};
+use crate::{
+    BOOT_SERVICES,
+    bench::{TPL_HIGH_LEVEL, efi_boot_services_table},
+    config::BenchConfig,
+    error::BenchError,
+    measure::Measurements,
+};
 
 /// Benchmarks checking the state of an already-signaled event (fast path).
-pub(crate) fn bench_check_event_signaled(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
+pub(crate) fn bench_check_event_signaled(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let event_handle = BOOT_SERVICES
             .create_event(EventType::NOTIFY_WAIT, Tpl::NOTIFY, Some(test_notify), ptr::null_mut())
-            .map_err(|e| BenchError::BenchSetup("Failed to create event", e))?;
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
         // Signal the event to set it to the signaled state.
-        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::BenchSetup("Failed to signal event", e))?;
+        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::setup("Failed to signal event", e))?;
 
         let start = Arch::cpu_count();
-        BOOT_SERVICES.check_event(event_handle).map_err(|e| BenchError::BenchTest("check_event failed", e))?;
+        BOOT_SERVICES.check_event(event_handle).map_err(|e| BenchError::measure("check_event failed", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
 
-        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::BenchCleanup("Failed to close event", e))?;
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
     }
     Ok(stats)
 }
 
 /// Benchmarks checking the state of an unsignaled event (slow path).
-pub(crate) fn bench_check_event_unsignaled(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
+pub(crate) fn bench_check_event_unsignaled(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let event_handle = BOOT_SERVICES
             .create_event(EventType::NOTIFY_WAIT, Tpl::NOTIFY, Some(test_notify), ptr::null_mut())
-            .map_err(|e| BenchError::BenchSetup("Failed to create event", e))?;
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
 
         let start = Arch::cpu_count();
         if let Err(e) = BOOT_SERVICES.check_event(event_handle) {
             // In this case a NOT_READY error is acceptable since the event is unsignaled.
             if e != efi::Status::SUCCESS && e != efi::Status::NOT_READY {
-                return Err(BenchError::BenchTest("check_event returned unexpected status", e));
+                return Err(BenchError::measure("check_event returned unexpected status", e));
             }
         }
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
 
-        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::BenchCleanup("Failed to close event", e))?;
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks a `check_event` polling loop that ends once the event is signaled, as boot loops do.
+/// The notify function signals the event after a pseudo-random number of calls, and the whole loop is timed.
+pub(crate) fn bench_check_event_poll_until_signaled(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    /// Upper bound for the number of `check_event` calls before the event is signaled.
+    const MAX_POLLS: u32 = 64;
+
+    /// Shared state between the polling loop and the notify function.
+    struct PollContext {
+        polls: AtomicU32,
+        signal_after: AtomicU32,
+    }
+
+    // Queued by every `check_event` call on the unsignaled event. Signals the event once enough polls have happened.
+    extern "efiapi" fn poll_notify(event: efi::Event, context: *mut PollContext) {
+        // SAFETY: The context points to the poll state on the benchmark's stack, which outlives the event.
+        let context = unsafe { &*context };
+        let polls = context.polls.fetch_add(1, Ordering::Relaxed) + 1;
+        if polls >= context.signal_after.load(Ordering::Relaxed) {
+            let _ = BOOT_SERVICES.signal_event(event);
+        }
+    }
+
+    let mut context = PollContext { polls: AtomicU32::new(0), signal_after: AtomicU32::new(0) };
+    // SAFETY: The context outlives the event, which is closed before returning.
+    let event_handle = unsafe {
+        BOOT_SERVICES.create_event_unchecked(
+            EventType::NOTIFY_WAIT,
+            Tpl::NOTIFY,
+            Some(poll_notify),
+            &mut context as *mut PollContext,
+        )
+    }
+    .map_err(|e| BenchError::setup("Failed to create event", e))?;
+
+    // Fixed-seed xorshift so every run polls the same sequence of counts.
+    let mut seed: u32 = 0x2545_f491;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        context.polls.store(0, Ordering::Relaxed);
+        context.signal_after.store(seed % MAX_POLLS + 1, Ordering::Relaxed);
+
+        let start = Arch::cpu_count();
+        loop {
+            match BOOT_SERVICES.check_event(event_handle) {
+                Ok(()) => break,
+                Err(efi::Status::NOT_READY) => (),
+                Err(e) => return Err(BenchError::measure("check_event returned unexpected status", e)),
+            }
+        }
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+        stats.update_aux("Polls until signaled", context.polls.load(Ordering::Relaxed) as f64);
+    }
+
+    BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    Ok(stats)
+}
+
+/// Benchmarks checking an unsignaled event whose notify function is queued but not yet dispatched.
+/// The TPL is held at `TPL_HIGH_LEVEL` so the queued notify cannot run, which exercises the event queue path.
+pub(crate) fn bench_check_event_pending_notify(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let event_handle = BOOT_SERVICES
+            .create_event(EventType::NOTIFY_WAIT, Tpl::CALLBACK, Some(test_notify), ptr::null_mut())
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
+
+        let old_tpl = BOOT_SERVICES.raise_tpl(TPL_HIGH_LEVEL);
+        // Checking an unsignaled `NOTIFY_WAIT` event queues its notify function, which stays pending at this TPL.
+        let queue_result = BOOT_SERVICES.check_event(event_handle);
+
+        let start = Arch::cpu_count();
+        let check_result = BOOT_SERVICES.check_event(event_handle);
+        let end = Arch::cpu_count();
+
+        // Restoring the TPL dispatches the pending notify function.
+        BOOT_SERVICES.restore_tpl(old_tpl);
+        // In this case a NOT_READY error is acceptable since the event is unsignaled.
+        match queue_result {
+            Ok(()) | Err(efi::Status::NOT_READY) => (),
+            Err(e) => return Err(BenchError::setup("check_event returned unexpected status", e)),
+        }
+        match check_result {
+            Ok(()) | Err(efi::Status::NOT_READY) => (),
+            Err(e) => return Err(BenchError::measure("check_event returned unexpected status", e)),
+        }
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
     }
     Ok(stats)
 }
 
 /// Benchmarks event creation performance.
-pub(crate) fn bench_create_event(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
+pub(crate) fn bench_create_event(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         let event_handle = BOOT_SERVICES
             .create_event(EventType::NOTIFY_WAIT, Tpl::NOTIFY, Some(test_notify), ptr::null_mut())
-            .map_err(|e| BenchError::BenchTest("Failed to create event", e))?;
+            .map_err(|e| BenchError::measure("Failed to create event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Clean up the created event.
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks creating a `NOTIFY_SIGNAL` event.
+pub(crate) fn bench_create_event_notify_signal(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    create_event_of_type(EventType::NOTIFY_SIGNAL, num_calls, config)
+}
+
+/// Benchmarks creating a plain `TIMER` event without a notify function.
+pub(crate) fn bench_create_event_timer(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    create_event_of_type(EventType::TIMER, num_calls, config)
+}
+
+/// Benchmarks creating a `TIMER | NOTIFY_SIGNAL` event, the type used for periodic callbacks.
+pub(crate) fn bench_create_event_timer_notify_signal(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    create_event_of_type(EventType::TIMER | EventType::NOTIFY_SIGNAL, num_calls, config)
+}
+
+/// Benchmarks creating a `RUNTIME` event, which the firmware must allocate from runtime memory.
+pub(crate) fn bench_create_event_runtime(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    create_event_of_type(EventType::RUNTIME, num_calls, config)
+}
+
+/// Times `create_event` for the given event type. A notify function is only passed for the `NOTIFY_*` types,
+/// which require one.
+fn create_event_of_type(
+    event_type: EventType,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+    let notify: Option<extern "efiapi" fn(efi::Event, *mut c_void)> =
+        if u32::from(event_type) & (efi::EVT_NOTIFY_SIGNAL | efi::EVT_NOTIFY_WAIT) != 0 {
+            Some(test_notify)
+        } else {
+            None
+        };
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let event_handle = BOOT_SERVICES
+            .create_event(event_type, Tpl::NOTIFY, notify, ptr::null_mut())
+            .map_err(|e| BenchError::measure("Failed to create event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Clean up the created event.
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+    Ok(stats)
+}
+
+/// Mock GUID for the event groups created by the `create_event_ex` benchmarks.
+const BENCH_CREATE_EVENT_GROUP: efi::Guid =
+    efi::Guid::from_fields(0x1234567a, 0x9abc, 0xdef0, 0x12, 0x34, &[0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0]);
+
+/// Benchmarks creating a grouped timer event without a notify function.
+/// This is the baseline for `bench_create_event_ex_with_notify`.
+pub(crate) fn bench_create_event_ex_no_notify(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let event_handle = BOOT_SERVICES
+            .create_event_ex(EventType::TIMER, Tpl::NOTIFY, None, ptr::null_mut::<c_void>(), &BENCH_CREATE_EVENT_GROUP)
+            .map_err(|e| BenchError::measure("Failed to create event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Clean up the created event.
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks `create_event_ex` with a null event group GUID, which creates an ungrouped event like `create_event`.
+/// The event matches the one created by `bench_create_event`, so the difference is the overhead of the `Ex` entry point.
+pub(crate) fn bench_create_event_ex_null_group(
+    handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+    // The `BootServices` wrapper only takes a GUID reference, so call the firmware directly.
+    let efi_boot_services = efi_boot_services_table(handle)?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let mut event_handle: efi::Event = ptr::null_mut();
+        let start = Arch::cpu_count();
+        let status = (efi_boot_services.create_event_ex)(
+            efi::EVT_NOTIFY_WAIT,
+            efi::TPL_NOTIFY,
+            Some(test_notify),
+            ptr::null(),
+            ptr::null(),
+            &mut event_handle,
+        );
         let end = Arch::cpu_count();
+        if status.is_error() {
+            return Err(BenchError::measure("Failed to create event", status));
+        }
         stats.update((end - start) as f64);
 
         // Clean up the created event.
-        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::BenchCleanup("Failed to close event", e))?;
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks creating a grouped timer event with a notify function.
+/// The difference from `bench_create_event_ex_no_notify` is the cost of registering the callback.
+pub(crate) fn bench_create_event_ex_with_notify(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let event_handle = BOOT_SERVICES
+            .create_event_ex(
+                EventType::TIMER | EventType::NOTIFY_SIGNAL,
+                Tpl::NOTIFY,
+                Some(test_notify),
+                ptr::null_mut(),
+                &BENCH_CREATE_EVENT_GROUP,
+            )
+            .map_err(|e| BenchError::measure("Failed to create event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Clean up the created event.
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
     }
     Ok(stats)
 }
 
 /// Benchmarks event closing performance.
-pub(crate) fn bench_close_event(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
+pub(crate) fn bench_close_event(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let event_handle = BOOT_SERVICES
             .create_event(EventType::NOTIFY_WAIT, Tpl::NOTIFY, Some(test_notify), ptr::null_mut())
-            .map_err(|e| BenchError::BenchSetup("Failed to create event", e))?;
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::measure("Failed to close event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    Ok(stats)
+}
+
+/// Benchmarks closing an unsignaled `NOTIFY_SIGNAL` event at `TPL_NOTIFY`.
+/// This is the baseline for `bench_close_event_signaled`.
+pub(crate) fn bench_close_event_unsignaled(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let event_handle = BOOT_SERVICES
+            .create_event(EventType::NOTIFY_SIGNAL, Tpl::CALLBACK, Some(test_notify), ptr::null_mut())
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
+
+        // Close at the same TPL as `bench_close_event_signaled` so the two are comparable.
+        let old_tpl = BOOT_SERVICES.raise_tpl(Tpl::NOTIFY);
+        let start = Arch::cpu_count();
+        let result = BOOT_SERVICES.close_event(event_handle);
+        let end = Arch::cpu_count();
+        BOOT_SERVICES.restore_tpl(old_tpl);
+        result.map_err(|e| BenchError::measure("Failed to close event", e))?;
+        stats.update((end - start) as f64);
+    }
+    Ok(stats)
+}
+
+/// Benchmarks closing a signaled `NOTIFY_SIGNAL` event whose notify function is still queued.
+/// The firmware must also remove the event from its dispatch queue.
+pub(crate) fn bench_close_event_signaled(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let event_handle = BOOT_SERVICES
+            .create_event(EventType::NOTIFY_SIGNAL, Tpl::CALLBACK, Some(test_notify), ptr::null_mut())
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
+
+        // Hold the TPL above the notify TPL so the notify function stays queued until the event is closed.
+        let old_tpl = BOOT_SERVICES.raise_tpl(Tpl::NOTIFY);
+        if let Err(e) = BOOT_SERVICES.signal_event(event_handle) {
+            BOOT_SERVICES.restore_tpl(old_tpl);
+            return Err(BenchError::setup("Failed to signal event", e));
+        }
         let start = Arch::cpu_count();
-        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::BenchTest("Failed to close event", e))?;
+        let result = BOOT_SERVICES.close_event(event_handle);
+        let end = Arch::cpu_count();
+        BOOT_SERVICES.restore_tpl(old_tpl);
+        result.map_err(|e| BenchError::measure("Failed to close event", e))?;
+        stats.update((end - start) as f64);
+    }
+    Ok(stats)
+}
+
+/// Benchmarks closing a `TPL_NOTIFY` event whose notify function was deferred by signaling it at `TPL_HIGH_LEVEL`.
+/// The firmware must remove the event from its deferred-notify queue so that the notify function never runs.
+/// The fraction of closed events whose notify function still ran is reported next to the cycles.
+pub(crate) fn bench_close_event_deferred_from_tpl_high(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Increments the counter that the context points to.
+    extern "efiapi" fn counter_notify(_event: efi::Event, context: *mut u64) {
+        // SAFETY: The context points to the counter on the benchmark's stack, which outlives the event.
+        unsafe { AtomicU64::from_ptr(context) }.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let mut counter: u64 = 0;
+    let counter_ptr = &mut counter as *mut u64;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // SAFETY: The counter outlives the event, which is closed within the same iteration.
+        let event_handle = unsafe {
+            BOOT_SERVICES.create_event_unchecked(
+                EventType::NOTIFY_SIGNAL,
+                Tpl::NOTIFY,
+                Some(counter_notify),
+                counter_ptr,
+            )
+        }
+        .map_err(|e| BenchError::setup("Failed to create event", e))?;
+        // SAFETY: The counter is only accessed atomically through `counter_ptr`, and it outlives the event.
+        let dispatched_before = unsafe { AtomicU64::from_ptr(counter_ptr) }.load(Ordering::Relaxed);
+
+        // Signal at `TPL_HIGH_LEVEL`, then drop back to `TPL_NOTIFY` to close the event, as `close_event` may not be
+        // called above `TPL_NOTIFY`. The notify function is not dispatched as its TPL is not above the current one.
+        let old_tpl = BOOT_SERVICES.raise_tpl(Tpl::NOTIFY);
+        let notify_tpl = BOOT_SERVICES.raise_tpl(TPL_HIGH_LEVEL);
+        let signal_result = BOOT_SERVICES.signal_event(event_handle);
+        BOOT_SERVICES.restore_tpl(notify_tpl);
+        if let Err(e) = signal_result {
+            BOOT_SERVICES.restore_tpl(old_tpl);
+            return Err(BenchError::setup("Failed to signal event", e));
+        }
+        let start = Arch::cpu_count();
+        let result = BOOT_SERVICES.close_event(event_handle);
+        let end = Arch::cpu_count();
+        BOOT_SERVICES.restore_tpl(old_tpl);
+        result.map_err(|e| BenchError::measure("Failed to close event", e))?;
+        stats.update((end - start) as f64);
+
+        // SAFETY: The counter is only accessed atomically through `counter_ptr`, and it outlives the event.
+        let dispatched_after = unsafe { AtomicU64::from_ptr(counter_ptr) }.load(Ordering::Relaxed);
+        stats.update_aux("Notify ran after close", if dispatched_after > dispatched_before { 1.0 } else { 0.0 });
+    }
+    Ok(stats)
+}
+
+/// Benchmarks closing a timer event with a pending timer.
+/// The firmware must also remove the event from its timer queue.
+pub(crate) fn bench_close_event_timer_pending(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // One second (in 100ns units). Long enough that the timer never expires during the benchmark.
+    const TIMER_TRIGGER_TIME: u64 = 10_000_000;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let event_handle = BOOT_SERVICES
+            .create_event(EventType::TIMER, Tpl::NOTIFY, None, ptr::null_mut::<c_void>())
+            .map_err(|e| BenchError::setup("Failed to create timer event", e))?;
+        BOOT_SERVICES
+            .set_timer(event_handle, EventTimerType::Relative, TIMER_TRIGGER_TIME)
+            .map_err(|e| BenchError::setup("Failed to set timer", e))?;
+
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::measure("Failed to close timer event", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
     }
@@ -96,27 +529,347 @@ pub(crate) fn bench_close_event(_handle: efi::Handle, num_calls: usize) -> Resul
 }
 
 /// Benchmarks individual event signaling.
-pub(crate) fn bench_signal_event(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
+pub(crate) fn bench_signal_event(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let event_handle = BOOT_SERVICES
             .create_event(EventType::NOTIFY_WAIT, Tpl::NOTIFY, Some(test_notify), ptr::null_mut())
-            .map_err(|e| BenchError::BenchSetup("Failed to create event", e))?;
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
+
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::measure("Failed to signal event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks signaling an event whose no-op notify function is dispatched on signal.
+/// Unlike `bench_signal_event`, the `NOTIFY_SIGNAL` event type makes the firmware dispatch the callback.
+pub(crate) fn bench_signal_event_notify_signal(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let event_handle = BOOT_SERVICES
+            .create_event(EventType::NOTIFY_SIGNAL, Tpl::NOTIFY, Some(test_notify), ptr::null_mut())
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
+
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::measure("Failed to signal event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+    Ok(stats)
+}
+
+/// Signals a `NOTIFY_SIGNAL` event with a `TPL_CALLBACK` notify function while running at `caller_tpl`.
+/// Below `TPL_CALLBACK` the notify function is dispatched within the signal, otherwise it is only queued.
+fn signal_notify_signal_event_at(
+    caller_tpl: Tpl,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let event_handle = BOOT_SERVICES
+            .create_event(EventType::NOTIFY_SIGNAL, Tpl::CALLBACK, Some(test_notify), ptr::null_mut())
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
+
+        let old_tpl = BOOT_SERVICES.raise_tpl(caller_tpl);
+        let start = Arch::cpu_count();
+        let result = BOOT_SERVICES.signal_event(event_handle);
+        let end = Arch::cpu_count();
+        // Restoring the TPL dispatches a deferred notify function.
+        BOOT_SERVICES.restore_tpl(old_tpl);
+        result.map_err(|e| BenchError::measure("Failed to signal event", e))?;
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks signaling a `TPL_CALLBACK` notify event from `TPL_APPLICATION`, which dispatches the notify immediately.
+pub(crate) fn bench_signal_event_tpl_application(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    signal_notify_signal_event_at(Tpl::APPLICATION, num_calls, config)
+}
+
+/// Benchmarks signaling a `TPL_CALLBACK` notify event from `TPL_CALLBACK`, which defers the notify until restore.
+pub(crate) fn bench_signal_event_tpl_callback(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    signal_notify_signal_event_at(Tpl::CALLBACK, num_calls, config)
+}
+
+/// Benchmarks signaling a `TPL_CALLBACK` notify event from `TPL_NOTIFY`, which defers the notify until restore.
+pub(crate) fn bench_signal_event_from_tpl_notify(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    signal_notify_signal_event_at(Tpl::NOTIFY, num_calls, config)
+}
+
+/// Benchmarks signaling a `TPL_CALLBACK` notify event from `TPL_HIGH_LEVEL`, with interrupts disabled, which defers
+/// the notify until restore.
+pub(crate) fn bench_signal_event_from_tpl_high(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    signal_notify_signal_event_at(TPL_HIGH_LEVEL, num_calls, config)
+}
+
+/// Benchmarks signaling a `TPL_NOTIFY` notify event from `TPL_APPLICATION`, which dispatches the notify immediately.
+/// The fraction of signals that dispatched the notify function before returning is reported next to the cycles.
+/// The `TPL_CALLBACK` notify TPL is covered by `bench_signal_event_tpl_application`.
+pub(crate) fn bench_signal_event_notify_tpl_notify(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Increments the counter that the context points to.
+    extern "efiapi" fn counter_notify(_event: efi::Event, context: *mut u64) {
+        // SAFETY: The context points to the counter on the benchmark's stack, which outlives the event.
+        unsafe { AtomicU64::from_ptr(context) }.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let mut counter: u64 = 0;
+    let counter_ptr = &mut counter as *mut u64;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // SAFETY: The counter outlives the event, which is closed within the same iteration.
+        let event_handle = unsafe {
+            BOOT_SERVICES.create_event_unchecked(
+                EventType::NOTIFY_SIGNAL,
+                Tpl::NOTIFY,
+                Some(counter_notify),
+                counter_ptr,
+            )
+        }
+        .map_err(|e| BenchError::setup("Failed to create event", e))?;
+
+        // SAFETY: The counter is only accessed atomically through `counter_ptr`, and it outlives the event.
+        let dispatched_before = unsafe { AtomicU64::from_ptr(counter_ptr) }.load(Ordering::Relaxed);
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::measure("Failed to signal event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+        // SAFETY: The counter is only accessed atomically through `counter_ptr`, and it outlives the event.
+        let dispatched_after = unsafe { AtomicU64::from_ptr(counter_ptr) }.load(Ordering::Relaxed);
+        stats.update_aux("Dispatched in signal", if dispatched_after > dispatched_before { 1.0 } else { 0.0 });
+
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks re-signaling an event that has already been signaled.
+/// Firmware that tracks an internal signaled state may make subsequent signals cheaper than the first one.
+pub(crate) fn bench_signal_event_resignal(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+    let event_handle = BOOT_SERVICES
+        .create_event(EventType::NOTIFY_SIGNAL, Tpl::NOTIFY, Some(test_notify), ptr::null_mut())
+        .map_err(|e| BenchError::setup("Failed to create event", e))?;
+    // Signal once so that every measured call is a re-signal.
+    BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::setup("Failed to signal event", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::measure("Failed to signal event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    Ok(stats)
+}
+
+/// Benchmarks signaling an event whose notify function does work through its context pointer.
+/// Comparing with `bench_signal_event_notify_signal` splits the callback cost into dispatch and work overhead.
+pub(crate) fn bench_signal_event_context_callback(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Increments the counter that the context points to.
+    extern "efiapi" fn counter_notify(_event: efi::Event, context: *mut u64) {
+        // SAFETY: The context points to the counter on the benchmark's stack, which outlives the event.
+        unsafe { AtomicU64::from_ptr(context) }.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let mut counter: u64 = 0;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // SAFETY: The counter outlives the event, which is closed within the same iteration.
+        let event_handle = unsafe {
+            BOOT_SERVICES.create_event_unchecked(
+                EventType::NOTIFY_SIGNAL,
+                Tpl::NOTIFY,
+                Some(counter_notify),
+                &mut counter as *mut u64,
+            )
+        }
+        .map_err(|e| BenchError::setup("Failed to create event", e))?;
 
         let start = Arch::cpu_count();
-        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::BenchTest("Failed to signal event", e))?;
+        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::measure("Failed to signal event", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
 
-        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::BenchCleanup("Failed to close event", e))?;
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
     }
+    log::debug!("Context callback was dispatched {} times for {} signals.", counter, num_calls);
+    Ok(stats)
+}
+
+/// Benchmarks the latency from `signal_event` to the entry of the dispatched notify function.
+/// The notify function records the cycle count at its entry into the context.
+pub(crate) fn bench_callback_dispatch_latency(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut callback_timestamp: u64 = 0;
+    callback_dispatch_latency_with_context(&mut callback_timestamp as *mut u64, Tpl::NOTIFY, num_calls, config)
+}
+
+/// Benchmarks the latency from `signal_event` to the entry of a `TPL_CALLBACK` notify function.
+/// The timestamp lives in an `AtomicU64` that the notify function writes through the context pointer.
+pub(crate) fn bench_event_dispatch_latency(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let callback_timestamp = AtomicU64::new(0);
+    callback_dispatch_latency_with_context(callback_timestamp.as_ptr(), Tpl::CALLBACK, num_calls, config)
+}
+
+/// Benchmarks the callback dispatch latency with the context in a page allocated above 4 GiB when possible.
+/// On multi-socket systems such memory may belong to a remote memory zone, which increases the callback latency.
+pub(crate) fn bench_create_event_remote_context(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    const FOUR_GIB: u64 = 0x1_0000_0000;
+
+    // Find the first page above 4 GiB in a conventional memory region, if the system has any.
+    let high_address = {
+        let memory_map =
+            BOOT_SERVICES.get_memory_map().map_err(|e| BenchError::setup("Failed to get memory map", e.0))?;
+        memory_map
+            .descriptors
+            .iter()
+            .filter(|descriptor| descriptor.r#type == efi::CONVENTIONAL_MEMORY)
+            .map(|descriptor| {
+                let end = descriptor.physical_start + descriptor.number_of_pages * UEFI_PAGE_SIZE as u64;
+                (descriptor.physical_start.max(FOUR_GIB), end)
+            })
+            .find(|(start, end)| start + UEFI_PAGE_SIZE as u64 <= *end)
+            .map(|(start, _)| start as usize)
+    };
+    let alloc_type = match high_address {
+        Some(address) => AllocType::Address(address),
+        None => {
+            log::info!("No free memory above 4 GiB, allocating the event context from any page.");
+            AllocType::AnyPage
+        }
+    };
+    let page = BOOT_SERVICES
+        .allocate_pages(alloc_type, EfiMemoryType::BootServicesData, 1)
+        .map_err(|e| BenchError::setup("Failed to allocate context page", e))?;
+
+    // The page is suitably aligned for a `u64` and is exclusively owned by this benchmark until it is freed below.
+    let mut result = callback_dispatch_latency_with_context(page as *mut u64, Tpl::NOTIFY, num_calls, config);
+    if let Ok(stats) = result.as_mut() {
+        stats.update_aux("Context address (GiB)", page as f64 / (FOUR_GIB / 4) as f64);
+    }
+
+    BOOT_SERVICES.free_pages(page, 1).map_err(|e| BenchError::cleanup("Failed to free context page", e))?;
+    result
+}
+
+/// Times `signal_event` until the entry of its `notify_tpl` notify function, which records the cycle count in the `u64` that
+/// `callback_timestamp` points to. The caller must keep the timestamp alive and access it only through this pointer.
+fn callback_dispatch_latency_with_context(
+    callback_timestamp: *mut u64,
+    notify_tpl: Tpl,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Stores the cycle count at callback entry in the context.
+    extern "efiapi" fn timestamp_notify(_event: efi::Event, context: *mut u64) {
+        let now = Arch::cpu_count();
+        // SAFETY: The context points to the caller's timestamp, which outlives the event.
+        unsafe { AtomicU64::from_ptr(context) }.store(now, Ordering::Relaxed);
+    }
+
+    // SAFETY: The timestamp outlives the event, which is closed before returning.
+    let event_handle = unsafe {
+        BOOT_SERVICES.create_event_unchecked(
+            EventType::NOTIFY_SIGNAL,
+            notify_tpl,
+            Some(timestamp_notify),
+            callback_timestamp,
+        )
+    }
+    .map_err(|e| BenchError::setup("Failed to create event", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // SAFETY: The timestamp is only accessed atomically through `callback_timestamp`, and it outlives the event.
+        unsafe { AtomicU64::from_ptr(callback_timestamp) }.store(0, Ordering::Relaxed);
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::measure("Failed to signal event", e))?;
+        // SAFETY: The timestamp is only accessed atomically through `callback_timestamp`, and it outlives the event.
+        let end = unsafe { AtomicU64::from_ptr(callback_timestamp) }.load(Ordering::Relaxed);
+        if end == 0 {
+            let _ = BOOT_SERVICES.close_event(event_handle);
+            return Err(BenchError::measure(
+                "Notify function was not dispatched by signal_event",
+                efi::Status::NOT_READY,
+            ));
+        }
+        stats.update((end - start) as f64);
+    }
+
+    BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
     Ok(stats)
 }
 
 /// Tests signaling multiple events as a group.
-pub(crate) fn bench_signal_event_group(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+pub(crate) fn bench_signal_event_group(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
 
     // No-op notify function. We want to measure only the signaling overhead.
     extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
@@ -136,19 +889,127 @@ pub(crate) fn bench_signal_event_group(_handle: efi::Handle, num_calls: usize) -
                 ptr::null_mut(),
                 &BENCH_EVENT_GROUP,
             )
-            .map_err(|e| BenchError::BenchSetup("Failed to create event", e))?;
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
         event_grp.push(event_handle);
 
         let start = Arch::cpu_count();
         // Signals the most recently created event in the group.
-        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::BenchTest("Failed to signal event", e))?;
+        BOOT_SERVICES.signal_event(event_handle).map_err(|e| BenchError::measure("Failed to signal event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    // Clean up all created events.
+    for event_handle in event_grp {
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks signaling an event group that always holds exactly 100 events.
+/// Unlike `bench_signal_event_group`, the fan-out stays fixed, so every iteration measures the same amount of work.
+pub(crate) fn bench_signal_event_group_fixed_100(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    const GROUP_SIZE: usize = 100;
+
+    // No-op notify function. We want to measure only the signaling overhead.
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+
+    // Use a mock GUID to avoid signalling real event groups.
+    const BENCH_FIXED_EVENT_GROUP: efi::Guid =
+        efi::Guid::from_fields(0x12345679, 0x9abc, 0xdef0, 0x12, 0x34, &[0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0]);
+
+    fn create_group() -> Result<Vec<efi::Event>, BenchError> {
+        let mut event_grp = Vec::with_capacity(GROUP_SIZE);
+        for _ in 0..GROUP_SIZE {
+            let event_handle = BOOT_SERVICES
+                .create_event_ex(
+                    EventType::NOTIFY_WAIT,
+                    Tpl::NOTIFY,
+                    Some(test_notify),
+                    ptr::null_mut(),
+                    &BENCH_FIXED_EVENT_GROUP,
+                )
+                .map_err(|e| BenchError::setup("Failed to create event", e))?;
+            event_grp.push(event_handle);
+        }
+        Ok(event_grp)
+    }
+
+    fn close_group(event_grp: Vec<efi::Event>) -> Result<(), BenchError> {
+        for event_handle in event_grp {
+            BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+        }
+        Ok(())
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    let mut event_grp = create_group()?;
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.signal_event(event_grp[0]).map_err(|e| BenchError::measure("Failed to signal event", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Rebuild the group so the next signal starts from 100 unsignaled events.
+        close_group(event_grp)?;
+        event_grp = create_group()?;
+    }
+
+    close_group(event_grp)?;
+
+    Ok(stats)
+}
+
+/// Benchmarks signaling a group of 100 `NOTIFY_SIGNAL` events of which `CALLBACK_EVENTS` notify at `TPL_CALLBACK` and
+/// the rest at `TPL_NOTIFY`, including the dispatch of their notify functions.
+/// Firmware that queues notifications per TPL has to split the group, so the cost depends on the mix.
+pub(crate) fn bench_signal_event_group_mixed_tpl<const CALLBACK_EVENTS: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    const GROUP_SIZE: usize = 100;
+
+    // No-op notify function. We want to measure only the signaling and dispatch overhead.
+    extern "efiapi" fn test_notify(_event: efi::Event, _context: *mut c_void) {}
+
+    // Use a mock GUID to avoid signalling real event groups.
+    const BENCH_MIXED_TPL_EVENT_GROUP: efi::Guid =
+        efi::Guid::from_fields(0x1234567b, 0x9abc, 0xdef0, 0x12, 0x34, &[0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0]);
+
+    let mut event_grp = Vec::with_capacity(GROUP_SIZE);
+    for i in 0..GROUP_SIZE {
+        // `TPL_APPLICATION` is not a valid notify TPL, so `TPL_CALLBACK` is the lowest level an event can notify at.
+        let notify_tpl = if i < CALLBACK_EVENTS { Tpl::CALLBACK } else { Tpl::NOTIFY };
+        let event_handle = BOOT_SERVICES
+            .create_event_ex(
+                EventType::NOTIFY_SIGNAL,
+                notify_tpl,
+                Some(test_notify),
+                ptr::null_mut(),
+                &BENCH_MIXED_TPL_EVENT_GROUP,
+            )
+            .map_err(|e| BenchError::setup("Failed to create event", e))?;
+        event_grp.push(event_handle);
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // The notify functions run before `signal_event` returns, which resets the events for the next iteration.
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.signal_event(event_grp[0]).map_err(|e| BenchError::measure("Failed to signal event", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
     }
 
     // Clean up all created events.
     for event_handle in event_grp {
-        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::BenchCleanup("Failed to close event", e))?;
+        BOOT_SERVICES.close_event(event_handle).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
     }
 
     Ok(stats)
@@ -5,54 +5,367 @@
 //! SPDX-License-Identifier: Apache-2.0
 //!
 
+use crate::alloc::vec::Vec;
+
 use mu_rust_helpers::perf_timer::{Arch, ArchFunctionality as _};
-use patina::boot_services::BootServices;
-use r_efi::efi;
-use rolling_stats::Stats;
+use patina::boot_services::{BootServices, protocol_handler::HandleSearchType};
+use r_efi::efi::{
+    self,
+    protocols::{device_path, file, simple_file_system},
+};
 
-use crate::{BOOT_SERVICES, error::BenchError};
+use crate::{BOOT_SERVICES, config::BenchConfig, error::BenchError, measure::Measurements};
 
 /// Benchmarks UEFI image execution performance through a no-op image that exits immediately.
 ///  As `start_image` and `exit` are difficult to bench individually, this benchmark combines them.
 pub(crate) fn bench_start_image_and_exit(
     parent_handle: efi::Handle,
     num_calls: usize,
-) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         // The image `NoopImage.efi` is a no-op image that exits immediately.
         let image_bytes = include_bytes!("../../resources/NoopImage.efi");
         let loaded_image_handle = BOOT_SERVICES
             .load_image(false, parent_handle, core::ptr::null_mut(), Some(image_bytes))
-            .map_err(|e| BenchError::BenchSetup("Failed to load image", e))?;
+            .map_err(|e| BenchError::setup("Failed to load image", e))?;
 
         let start = Arch::cpu_count();
         // This also includes `exit` as the image exits immediately.
         BOOT_SERVICES
             .start_image(loaded_image_handle)
-            .map_err(|e| BenchError::BenchTest("Failed to start image", e.0))?;
+            .map_err(|e| BenchError::measure("Failed to start image", e.0))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
     }
     Ok(stats)
 }
 
+/// Benchmarks UEFI image execution performance through an image that calls `allocate_pool` and `free_pool` once
+/// before exiting. This resembles a minimal driver initialization, which re-enters boot services from the image.
+pub(crate) fn bench_start_image_and_exit_minimal_call(
+    parent_handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // The image `MinimalCallImage.efi` allocates and frees a small pool buffer, then exits with the resulting status.
+        let image_bytes = include_bytes!("../../resources/MinimalCallImage.efi");
+        let loaded_image_handle = BOOT_SERVICES
+            .load_image(false, parent_handle, core::ptr::null_mut(), Some(image_bytes))
+            .map_err(|e| BenchError::setup("Failed to load image", e))?;
+
+        let start = Arch::cpu_count();
+        // This also includes `exit` as the image exits right after its boot services calls.
+        BOOT_SERVICES
+            .start_image(loaded_image_handle)
+            .map_err(|e| BenchError::measure("Failed to start image", e.0))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    Ok(stats)
+}
+
+/// Benchmarks UEFI image execution performance through an image that returns `EFI_ABORTED` from its entry point.
+/// Some firmware walks a cleanup path when an image returns an error that is not taken when it returns success.
+pub(crate) fn bench_start_failed_image(
+    parent_handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // The image `FailImage.efi` returns `EFI_ABORTED` from its entry point without calling boot services.
+        let image_bytes = include_bytes!("../../resources/FailImage.efi");
+        let loaded_image_handle = BOOT_SERVICES
+            .load_image(false, parent_handle, core::ptr::null_mut(), Some(image_bytes))
+            .map_err(|e| BenchError::setup("Failed to load image", e))?;
+
+        let start = Arch::cpu_count();
+        let result = BOOT_SERVICES.start_image(loaded_image_handle);
+        let end = Arch::cpu_count();
+        match result {
+            Err((efi::Status::ABORTED, _)) => stats.update((end - start) as f64),
+            Err((status, _)) => return Err(BenchError::measure("Failed image returned an unexpected status", status)),
+            Ok(()) => {
+                return Err(BenchError::measure("Failed image unexpectedly succeeded", efi::Status::SUCCESS));
+            }
+        }
+    }
+    Ok(stats)
+}
+
 /// Measures UEFI image loading performance using a no-op image.
-pub(crate) fn bench_load_image(parent_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+pub(crate) fn bench_load_image(
+    parent_handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let image_bytes = include_bytes!("../../resources/NoopImage.efi");
         let start = Arch::cpu_count();
         let _loaded_image_handle = BOOT_SERVICES
             .load_image(false, parent_handle, core::ptr::null_mut(), Some(image_bytes))
-            .map_err(|e| BenchError::BenchTest("Failed to load image", e))?;
+            .map_err(|e| BenchError::measure("Failed to load image", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
 
         // Unload the image to avoid resource leaks.
         BOOT_SERVICES
             .unload_image(_loaded_image_handle)
-            .map_err(|e| BenchError::BenchCleanup("Failed to unload image", e))?;
+            .map_err(|e| BenchError::cleanup("Failed to unload image", e))?;
+    }
+    Ok(stats)
+}
+
+/// Measures UEFI image loading performance with `boot_policy` set, as if requested by the boot manager.
+/// The UEFI spec ignores `boot_policy` when a source buffer is given, but some firmware builds still pass it
+/// through the security architecture, resulting in a different code path.
+pub(crate) fn bench_load_image_boot_policy_true(
+    parent_handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let image_bytes = include_bytes!("../../resources/NoopImage.efi");
+        let start = Arch::cpu_count();
+        let loaded_image_handle = BOOT_SERVICES
+            .load_image(true, parent_handle, core::ptr::null_mut(), Some(image_bytes))
+            .map_err(|e| BenchError::measure("Failed to load image", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Unload the image to avoid resource leaks.
+        BOOT_SERVICES
+            .unload_image(loaded_image_handle)
+            .map_err(|e| BenchError::cleanup("Failed to unload image", e))?;
+    }
+    Ok(stats)
+}
+
+/// Measures UEFI image loading performance for a no-op image with a large section table.
+/// `LargeHeaderImage.efi` carries 32 extra one-byte sections, so the loader has more PE headers to parse and validate.
+pub(crate) fn bench_load_image_large_header(
+    parent_handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let image_bytes = include_bytes!("../../resources/LargeHeaderImage.efi");
+        let start = Arch::cpu_count();
+        let loaded_image_handle = BOOT_SERVICES
+            .load_image(false, parent_handle, core::ptr::null_mut(), Some(image_bytes))
+            .map_err(|e| BenchError::measure("Failed to load image", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Unload the image to avoid resource leaks.
+        BOOT_SERVICES
+            .unload_image(loaded_image_handle)
+            .map_err(|e| BenchError::cleanup("Failed to unload image", e))?;
+    }
+    Ok(stats)
+}
+
+/// Measures UEFI image loading performance for an image with separate `.data` and `.bss` sections.
+/// The loader has to copy the initialized data and zero-fill the uninitialized section, unlike for the no-op image.
+pub(crate) fn bench_load_image_with_sections(
+    parent_handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let image_bytes = include_bytes!("../../resources/TestImageWithSections.efi");
+        let start = Arch::cpu_count();
+        let loaded_image_handle = BOOT_SERVICES
+            .load_image(false, parent_handle, core::ptr::null_mut(), Some(image_bytes))
+            .map_err(|e| BenchError::measure("Failed to load image", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Unload the image to avoid resource leaks.
+        BOOT_SERVICES
+            .unload_image(loaded_image_handle)
+            .map_err(|e| BenchError::cleanup("Failed to unload image", e))?;
+    }
+    Ok(stats)
+}
+
+/// Path of the file that `bench_load_image_from_filesystem` writes the no-op image to.
+const FS_IMAGE_PATH: &str = "\\NoopImageBench.efi";
+
+/// Returns the null-terminated UTF-16 form of [`FS_IMAGE_PATH`].
+fn fs_image_path_utf16() -> Vec<u16> {
+    FS_IMAGE_PATH.encode_utf16().chain(core::iter::once(0)).collect()
+}
+
+/// Returns the device path of `handle` as bytes, without the terminating end node.
+fn device_path_bytes(handle: efi::Handle) -> Result<Vec<u8>, BenchError> {
+    // SAFETY: The device path is only read, and stays installed for the duration of the benchmark.
+    let path = unsafe { BOOT_SERVICES.handle_protocol_unchecked(handle, &device_path::PROTOCOL_GUID) }
+        .map_err(|e| BenchError::setup("Failed to get device path of file system", e))? as *const u8;
+
+    let mut len = 0;
+    loop {
+        // SAFETY: A device path is a sequence of nodes, each starting with a 4-byte header, terminated by an end node.
+        let node = unsafe { &*(path.add(len) as *const device_path::Protocol) };
+        if node.r#type == device_path::TYPE_END && node.sub_type == device_path::End::SUBTYPE_ENTIRE {
+            break;
+        }
+        len += u16::from_le_bytes(node.length) as usize;
+    }
+    // SAFETY: `len` bytes of the device path were walked above.
+    Ok(unsafe { core::slice::from_raw_parts(path, len) }.to_vec())
+}
+
+/// Returns whether the device path of `handle` contains a RAM disk node. A handle without a device path is not a RAM
+/// disk.
+fn is_ram_disk(handle: efi::Handle) -> bool {
+    let Ok(path) = device_path_bytes(handle) else {
+        return false;
+    };
+    let mut offset = 0;
+    while offset < path.len() {
+        if path[offset] == device_path::TYPE_MEDIA && path[offset + 1] == device_path::Media::SUBTYPE_RAM_DISK {
+            return true;
+        }
+        offset += u16::from_le_bytes([path[offset + 2], path[offset + 3]]) as usize;
+    }
+    false
+}
+
+/// Opens the root directory of the file system on `handle`.
+fn open_root(handle: efi::Handle) -> Result<*mut file::Protocol, BenchError> {
+    // SAFETY: The file system protocol is only used while the benchmark runs.
+    let fs = unsafe { BOOT_SERVICES.handle_protocol::<simple_file_system::Protocol>(handle) }
+        .map_err(|e| BenchError::setup("Failed to get simple file system", e))?;
+    let mut root = core::ptr::null_mut();
+    match (fs.open_volume)(fs, &mut root) {
+        s if s.is_error() => Err(BenchError::setup("Failed to open volume", s)),
+        _ => Ok(root),
+    }
+}
+
+/// Writes `image_bytes` to [`FS_IMAGE_PATH`] on the file system on `handle`.
+fn write_fs_image(handle: efi::Handle, image_bytes: &[u8]) -> Result<(), BenchError> {
+    let root = open_root(handle)?;
+    let mut path = fs_image_path_utf16();
+    let mut image_file = core::ptr::null_mut();
+    let mut len = image_bytes.len();
+    // SAFETY: `root` is a valid open directory and `image_file` is only used while it is open.
+    let status = unsafe {
+        match ((*root).open)(
+            root,
+            &mut image_file,
+            path.as_mut_ptr(),
+            file::MODE_READ | file::MODE_WRITE | file::MODE_CREATE,
+            0,
+        ) {
+            s if s.is_error() => s,
+            _ => {
+                let status = ((*image_file).write)(image_file, &mut len, image_bytes.as_ptr() as *mut _);
+                ((*image_file).close)(image_file);
+                status
+            }
+        }
+    };
+    // SAFETY: `root` is a valid open directory.
+    unsafe { ((*root).close)(root) };
+    match status {
+        efi::Status::WRITE_PROTECTED | efi::Status::ACCESS_DENIED => {
+            Err(BenchError::unsupported("RAM disk file system is read-only"))
+        }
+        s if s.is_error() => Err(BenchError::setup("Failed to write image to file system", s)),
+        _ => Ok(()),
+    }
+}
+
+/// Deletes [`FS_IMAGE_PATH`] from the file system on `handle`.
+fn delete_fs_image(handle: efi::Handle) -> Result<(), BenchError> {
+    let root = open_root(handle)?;
+    let mut path = fs_image_path_utf16();
+    let mut image_file = core::ptr::null_mut();
+    // SAFETY: `root` is a valid open directory. Deleting the file also closes its handle.
+    let status = unsafe {
+        match ((*root).open)(root, &mut image_file, path.as_mut_ptr(), file::MODE_READ | file::MODE_WRITE, 0) {
+            s if s.is_error() => s,
+            _ => ((*image_file).delete)(image_file),
+        }
+    };
+    // SAFETY: `root` is a valid open directory.
+    unsafe { ((*root).close)(root) };
+    match status {
+        s if s.is_error() => Err(BenchError::cleanup("Failed to delete image from file system", s)),
+        _ => Ok(()),
+    }
+}
+
+/// Measures UEFI image loading performance from a file system through a device path, instead of a memory buffer.
+/// The no-op image is written to a RAM disk, so the benchmark never writes to persistent boot media. The benchmark is
+/// skipped if no writable RAM disk file system is available.
+pub(crate) fn bench_load_image_from_filesystem(
+    parent_handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let fs_handles =
+        match BOOT_SERVICES.locate_handle_buffer(HandleSearchType::ByProtocol(&simple_file_system::PROTOCOL_GUID)) {
+            Ok(handles) => handles,
+            Err(efi::Status::NOT_FOUND) => return Err(BenchError::unsupported("No simple file system available")),
+            Err(e) => return Err(BenchError::setup("Failed to locate simple file systems", e)),
+        };
+    let fs_handle = fs_handles
+        .iter()
+        .copied()
+        .find(|&handle| is_ram_disk(handle))
+        .ok_or(BenchError::unsupported("No RAM disk file system available"))?;
+
+    let image_bytes = include_bytes!("../../resources/NoopImage.efi");
+    write_fs_image(fs_handle, image_bytes)?;
+
+    // The image file is deleted whether or not the measurement succeeded.
+    let result = load_fs_image(parent_handle, fs_handle, num_calls, config);
+    let deleted = delete_fs_image(fs_handle);
+    let stats = result?;
+    deleted?;
+    Ok(stats)
+}
+
+/// Times loading the image at [`FS_IMAGE_PATH`] on the file system on `fs_handle` through its device path.
+fn load_fs_image(
+    parent_handle: efi::Handle,
+    fs_handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Build the device path of the image file: the file system device path followed by a file path node.
+    let path = fs_image_path_utf16();
+    let node_len = 4 + path.len() * 2;
+    let mut image_path = device_path_bytes(fs_handle)?;
+    image_path.extend_from_slice(&[device_path::TYPE_MEDIA, device_path::Media::SUBTYPE_FILE_PATH]);
+    image_path.extend_from_slice(&(node_len as u16).to_le_bytes());
+    image_path.extend(path.iter().flat_map(|c| c.to_le_bytes()));
+    image_path.extend_from_slice(&[device_path::TYPE_END, device_path::End::SUBTYPE_ENTIRE, 4, 0]);
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let loaded_image_handle = BOOT_SERVICES
+            .load_image(false, parent_handle, image_path.as_mut_ptr() as *mut device_path::Protocol, None)
+            .map_err(|e| BenchError::measure("Failed to load image from file system", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Unload the image to avoid resource leaks.
+        BOOT_SERVICES
+            .unload_image(loaded_image_handle)
+            .map_err(|e| BenchError::cleanup("Failed to unload image", e))?;
     }
     Ok(stats)
 }
@@ -5,6 +5,8 @@
 //! SPDX-License-Identifier: Apache-2.0
 //!
 
+use core::ffi::c_void;
+
 use mu_rust_helpers::perf_timer::{Arch, ArchFunctionality as _};
 use patina::{
     base::UEFI_PAGE_SIZE,
@@ -12,55 +14,306 @@ use patina::{
     efi_types::EfiMemoryType,
 };
 use r_efi::efi;
-use rolling_stats::Stats;
 
-use crate::{BOOT_SERVICES, error::BenchError};
+use crate::{
+    BOOT_SERVICES, bench::record_window_throughput, config::BenchConfig, error::BenchError, measure::Measurements,
+};
 
 /// Benchmarks page-level memory allocation.
-pub(crate) fn bench_allocate_pages(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+pub(crate) fn bench_allocate_pages(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         // Use `BOOT_SERVICES_DATA` as it is commonly allocated during boot services/driver initialization.
         let pages = BOOT_SERVICES
             .allocate_pages(boot_services::allocation::AllocType::AnyPage, EfiMemoryType::BootServicesData, 1)
-            .map_err(|e| BenchError::BenchTest("Failed to allocate pages", e))?;
+            .map_err(|e| BenchError::measure("Failed to allocate pages", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES.free_pages(pages, 1).map_err(|e| BenchError::cleanup("Failed to free pages", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks page allocation at a fixed physical address, as OS loaders do when placing a kernel.
+/// The firmware has to verify that exactly the requested page is free rather than picking any free page.
+pub(crate) fn bench_allocate_pages_at_address(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Find the last page of a conventional memory region above 1 MiB to request on every iteration.
+    let address = {
+        let memory_map =
+            BOOT_SERVICES.get_memory_map().map_err(|e| BenchError::setup("Failed to get memory map", e.0))?;
+        memory_map
+            .descriptors
+            .iter()
+            .rev()
+            .find(|descriptor| {
+                descriptor.r#type == efi::CONVENTIONAL_MEMORY
+                    && descriptor.number_of_pages > 0
+                    && descriptor.physical_start >= 0x10_0000
+            })
+            .map(|descriptor| descriptor.physical_start + (descriptor.number_of_pages - 1) * UEFI_PAGE_SIZE as u64)
+            .ok_or(BenchError::setup("Failed to find a free page", efi::Status::NOT_FOUND))? as usize
+    };
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let pages = BOOT_SERVICES
+            .allocate_pages(boot_services::allocation::AllocType::Address(address), EfiMemoryType::BootServicesData, 1)
+            .map_err(|e| BenchError::measure("Failed to allocate pages at address", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES.free_pages(pages, 1).map_err(|e| BenchError::cleanup("Failed to free pages", e))?;
+    }
+    Ok(stats)
+}
+
+/// Highest address of the conventional memory range below 1 MiB used by real-mode code.
+const BELOW_1MB_MAX_ADDRESS: usize = 0xF_FFFF;
+
+/// Benchmarks page allocation below 1 MiB, as OS loaders do for real-mode trampolines.
+/// Some firmware serves this range from a separate allocator.
+pub(crate) fn bench_allocate_pages_below_1mb(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let pages = BOOT_SERVICES
+            .allocate_pages(
+                boot_services::allocation::AllocType::MaxAddress(BELOW_1MB_MAX_ADDRESS),
+                EfiMemoryType::BootServicesData,
+                1,
+            )
+            .map_err(|e| BenchError::measure("Failed to allocate pages below 1 MiB", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
 
-        BOOT_SERVICES.free_pages(pages, 1).map_err(|e| BenchError::BenchCleanup("Failed to free pages", e))?;
+        BOOT_SERVICES.free_pages(pages, 1).map_err(|e| BenchError::cleanup("Failed to free pages", e))?;
     }
     Ok(stats)
 }
 
 /// Benchmarks pool memory allocation.
-pub(crate) fn bench_allocate_pool(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+pub(crate) fn bench_allocate_pool(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         // Use `BOOT_SERVICES_DATA` as it is commonly allocated during boot services/driver initialization.
         let pool = BOOT_SERVICES
             .allocate_pool(EfiMemoryType::BootServicesData, UEFI_PAGE_SIZE / 4)
-            .map_err(|e| BenchError::BenchTest("Failed to allocate pool", e))?;
+            .map_err(|e| BenchError::measure("Failed to allocate pool", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
 
-        BOOT_SERVICES.free_pool(pool).map_err(|e| BenchError::BenchCleanup("Failed to free pool", e))?;
+        BOOT_SERVICES.free_pool(pool).map_err(|e| BenchError::cleanup("Failed to free pool", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks a zero-byte pool allocation, whose behavior the UEFI specification leaves undefined.
+/// Both `SUCCESS` and `INVALID_PARAMETER` are accepted, and which one the firmware returned is recorded as well.
+pub(crate) fn bench_allocate_pool_zero_bytes(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let result = BOOT_SERVICES.allocate_pool(EfiMemoryType::BootServicesData, 0);
+        let end = Arch::cpu_count();
+        match result {
+            Ok(pool) => {
+                stats.update((end - start) as f64);
+                stats.update_aux("Zero-size allocation succeeded", 1.0);
+                BOOT_SERVICES.free_pool(pool).map_err(|e| BenchError::cleanup("Failed to free pool", e))?;
+            }
+            Err(efi::Status::INVALID_PARAMETER) => {
+                stats.update((end - start) as f64);
+                stats.update_aux("Zero-size allocation succeeded", 0.0);
+            }
+            Err(e) => return Err(BenchError::measure("Failed to allocate pool", e)),
+        }
+    }
+    Ok(stats)
+}
+
+/// Benchmarks pool memory allocation on a fragmented pool.
+/// Repeated allocation and free of the same size keeps a single pool slot hot, which a fully booted system rarely sees.
+pub(crate) fn bench_allocate_pool_fragmented(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    const SMALL_SIZE: usize = 64;
+    const LARGE_SIZE: usize = 128;
+    const STRIDES: usize = 64;
+
+    // Fragment the pool with a stride pattern of small allocations separated by freed larger blocks.
+    let mut small_blocks = [core::ptr::null_mut::<u8>(); STRIDES];
+    for block in small_blocks.iter_mut() {
+        *block = BOOT_SERVICES
+            .allocate_pool(EfiMemoryType::BootServicesData, SMALL_SIZE)
+            .map_err(|e| BenchError::setup("Failed to allocate pool", e))?;
+        let large_block = BOOT_SERVICES
+            .allocate_pool(EfiMemoryType::BootServicesData, LARGE_SIZE)
+            .map_err(|e| BenchError::setup("Failed to allocate pool", e))?;
+        BOOT_SERVICES.free_pool(large_block).map_err(|e| BenchError::setup("Failed to free pool", e))?;
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let pool = BOOT_SERVICES
+            .allocate_pool(EfiMemoryType::BootServicesData, SMALL_SIZE)
+            .map_err(|e| BenchError::measure("Failed to allocate pool", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES.free_pool(pool).map_err(|e| BenchError::cleanup("Failed to free pool", e))?;
+    }
+
+    for block in small_blocks {
+        BOOT_SERVICES.free_pool(block).map_err(|e| BenchError::cleanup("Failed to free pool", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks the pool allocation that no longer fits in the pool page of the previous allocation, which usually makes
+/// the pool allocator take a new page.
+pub(crate) fn bench_allocate_pool_page_boundary(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    allocate_pool_across_page_boundary(num_calls, true, config)
+}
+
+/// Benchmarks the pool allocation that follows a page-crossing allocation and lands on the same, freshly used page.
+/// Compared against `bench_allocate_pool_page_boundary`, this isolates the cost of taking a new pool page.
+pub(crate) fn bench_allocate_pool_clean_page(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    allocate_pool_across_page_boundary(num_calls, false, config)
+}
+
+/// Makes 64-byte pool allocations until one lands on a different page than the allocation before it, followed by one
+/// that lands on the same page as that page-crossing allocation. Pool headers, the firmware's pool layout, and the
+/// direction in which it carves allocations out of a page are not known, so the page crossing is detected from the
+/// returned addresses. Records the page-crossing allocation if `crossing` is set, otherwise the allocation after it.
+fn allocate_pool_across_page_boundary(
+    num_calls: usize,
+    crossing: bool,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    const BLOCK_SIZE: usize = 64;
+    // Enough blocks to fill several pages, which bounds the search for the boundary.
+    const MAX_BLOCKS: usize = 8 * UEFI_PAGE_SIZE / BLOCK_SIZE;
+
+    let page_of = |block: *mut u8| block as usize & !(UEFI_PAGE_SIZE - 1);
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let mut blocks = [core::ptr::null_mut::<u8>(); MAX_BLOCKS];
+        blocks[0] = BOOT_SERVICES
+            .allocate_pool(EfiMemoryType::BootServicesData, BLOCK_SIZE)
+            .map_err(|e| BenchError::setup("Failed to allocate pool", e))?;
+        let mut count = 1;
+
+        // Cycles and page of the most recent page-crossing allocation.
+        let mut crossed: Option<(u64, usize)> = None;
+        let mut sample = None;
+        while count < MAX_BLOCKS {
+            let start = Arch::cpu_count();
+            let block = BOOT_SERVICES
+                .allocate_pool(EfiMemoryType::BootServicesData, BLOCK_SIZE)
+                .map_err(|e| BenchError::measure("Failed to allocate pool", e))?;
+            let end = Arch::cpu_count();
+            let previous_page = page_of(blocks[count - 1]);
+            blocks[count] = block;
+            count += 1;
+
+            match crossed {
+                Some((crossing_cycles, page)) if page_of(block) == page => {
+                    sample = Some(if crossing { crossing_cycles } else { end - start });
+                    break;
+                }
+                _ if page_of(block) != previous_page => crossed = Some((end - start, page_of(block))),
+                _ => {}
+            }
+        }
+
+        for block in &blocks[..count] {
+            BOOT_SERVICES.free_pool(*block).map_err(|e| BenchError::cleanup("Failed to free pool", e))?;
+        }
+
+        let cycles = sample.ok_or(BenchError::measure(
+            "No pool allocation crossed into a new page within the allocation limit",
+            efi::Status::NOT_FOUND,
+        ))?;
+        stats.update(cycles as f64);
     }
     Ok(stats)
 }
 
 /// Benchmarks page memory deallocation.
-pub(crate) fn bench_free_pages(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+pub(crate) fn bench_free_pages(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         // Use `BOOT_SERVICES_DATA` as it is commonly allocated during boot services/driver initialization.
         let pages = BOOT_SERVICES
             .allocate_pages(boot_services::allocation::AllocType::AnyPage, EfiMemoryType::BootServicesData, 1)
-            .map_err(|e| BenchError::BenchSetup("Failed to allocate pages", e))?;
+            .map_err(|e| BenchError::setup("Failed to allocate pages", e))?;
+
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.free_pages(pages, 1).map_err(|e| BenchError::measure("Failed to free pages", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    Ok(stats)
+}
+
+/// Benchmarks freeing pages allocated below 1 MiB.
+pub(crate) fn bench_free_pages_below_1mb(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let pages = BOOT_SERVICES
+            .allocate_pages(
+                boot_services::allocation::AllocType::MaxAddress(BELOW_1MB_MAX_ADDRESS),
+                EfiMemoryType::BootServicesData,
+                1,
+            )
+            .map_err(|e| BenchError::setup("Failed to allocate pages below 1 MiB", e))?;
 
         let start = Arch::cpu_count();
-        BOOT_SERVICES.free_pages(pages, 1).map_err(|e| BenchError::BenchTest("Failed to free pages", e))?;
+        BOOT_SERVICES.free_pages(pages, 1).map_err(|e| BenchError::measure("Failed to free pages", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
     }
@@ -68,27 +321,97 @@ pub(crate) fn bench_free_pages(_handle: efi::Handle, num_calls: usize) -> Result
 }
 
 /// Benchmarks pool memory deallocation.
-pub(crate) fn bench_free_pool(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+pub(crate) fn bench_free_pool(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         // Use `BOOT_SERVICES_DATA` as it is commonly allocated during boot services/driver initialization.
         let pool = BOOT_SERVICES
             .allocate_pool(EfiMemoryType::BootServicesData, UEFI_PAGE_SIZE / 4)
-            .map_err(|e| BenchError::BenchSetup("Failed to allocate pool", e))?;
+            .map_err(|e| BenchError::setup("Failed to allocate pool", e))?;
+
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.free_pool(pool).map_err(|e| BenchError::measure("Failed to free pool", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    Ok(stats)
+}
 
+/// Benchmarks `free_pool` on the base address of a page allocation, which is not a pool buffer.
+/// Firmware is expected to detect the missing pool header and return `INVALID_PARAMETER`, making this a conformance probe.
+/// Other outcomes are reported in the aux statistics rather than failing the benchmark. Firmware without the check may
+/// fault on the pointer, so this is a fault probe that only runs when enabled in the configuration.
+pub(crate) fn bench_free_pool_wrong_pointer(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let page = BOOT_SERVICES
+        .allocate_pages(boot_services::allocation::AllocType::AnyPage, EfiMemoryType::BootServicesData, 1)
+        .map_err(|e| BenchError::setup("Failed to allocate pages", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    let mut unexpected_warned = false;
+    for _ in 0..num_calls {
         let start = Arch::cpu_count();
-        BOOT_SERVICES.free_pool(pool).map_err(|e| BenchError::BenchTest("Failed to free pool", e))?;
+        let result = BOOT_SERVICES.free_pool(page as *mut u8);
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
+        stats.update_aux("Rejected as invalid", if result == Err(efi::Status::INVALID_PARAMETER) { 1.0 } else { 0.0 });
+        match result {
+            Err(efi::Status::INVALID_PARAMETER) => {}
+            Err(e) => {
+                if !unexpected_warned {
+                    log::warn!("Free pool of a page allocation returned {:?} instead of INVALID_PARAMETER.", e);
+                    unexpected_warned = true;
+                }
+            }
+            // The page is left allocated, as the firmware may now consider it free pool memory.
+            Ok(()) => {
+                log::warn!("Free pool of a page allocation unexpectedly succeeded, leaking the page.");
+                return Ok(stats);
+            }
+        }
+    }
+
+    BOOT_SERVICES.free_pages(page, 1).map_err(|e| BenchError::cleanup("Failed to free pages", e))?;
+    Ok(stats)
+}
+
+/// Benchmarks pool allocation throughput as the number of allocate/free pairs completed in a 10 ms window.
+/// Each iteration measures one window, giving system designers a peak-throughput number instead of a per-call latency.
+pub(crate) fn bench_alloc_free_throughput(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    const WINDOW_MS: u64 = 10;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        record_window_throughput(&mut stats, "Alloc/free pairs per ms", WINDOW_MS, || {
+            let pool = BOOT_SERVICES
+                .allocate_pool(EfiMemoryType::BootServicesData, UEFI_PAGE_SIZE / 4)
+                .map_err(|e| BenchError::measure("Failed to allocate pool", e))?;
+            BOOT_SERVICES.free_pool(pool).map_err(|e| BenchError::measure("Failed to free pool", e))
+        })?;
     }
     Ok(stats)
 }
 
 /// Benchmarks memory copying performance.
-pub(crate) fn bench_copy_mem(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
+pub(crate) fn bench_copy_mem(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     let src: u64 = 5678;
     let mut dst: u64 = 1234;
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         BOOT_SERVICES.copy_mem::<u64>(&mut dst, &src);
@@ -98,10 +421,114 @@ pub(crate) fn bench_copy_mem(_handle: efi::Handle, num_calls: usize) -> Result<S
     Ok(stats)
 }
 
+/// Benchmarks copying a 4 KiB buffer through the firmware's `CopyMem`.
+pub(crate) fn bench_copy_mem_4k(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let src: [u8; UEFI_PAGE_SIZE] = [0x5A; UEFI_PAGE_SIZE];
+    let mut dst: [u8; UEFI_PAGE_SIZE] = [0; UEFI_PAGE_SIZE];
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.copy_mem::<[u8; UEFI_PAGE_SIZE]>(&mut dst, &src);
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    Ok(stats)
+}
+
+/// Size of the copies in the same-page and different-page `CopyMem` benchmarks, so both halves fit in one page.
+const HALF_PAGE_SIZE: usize = UEFI_PAGE_SIZE / 2;
+
+/// Benchmarks copying half a page into the other half of the same 4 KiB page through the firmware's `CopyMem`.
+pub(crate) fn bench_copy_mem_same_page(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let page = BOOT_SERVICES
+        .allocate_pages(boot_services::allocation::AllocType::AnyPage, EfiMemoryType::BootServicesData, 1)
+        .map_err(|e| BenchError::setup("Failed to allocate pages", e))?;
+
+    let stats = copy_mem_half_page(page, page + HALF_PAGE_SIZE, num_calls, config);
+
+    BOOT_SERVICES.free_pages(page, 1).map_err(|e| BenchError::cleanup("Failed to free pages", e))?;
+    Ok(stats)
+}
+
+/// Benchmarks copying half a page between two separately allocated 4 KiB pages through the firmware's `CopyMem`.
+/// Comparing with `bench_copy_mem_same_page` shows the TLB and cache miss overhead of touching a second page.
+pub(crate) fn bench_copy_mem_different_pages(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let src_page = BOOT_SERVICES
+        .allocate_pages(boot_services::allocation::AllocType::AnyPage, EfiMemoryType::BootServicesData, 1)
+        .map_err(|e| BenchError::setup("Failed to allocate source page", e))?;
+    let dst_page = BOOT_SERVICES
+        .allocate_pages(boot_services::allocation::AllocType::AnyPage, EfiMemoryType::BootServicesData, 1)
+        .map_err(|e| BenchError::setup("Failed to allocate destination page", e))?;
+
+    let stats = copy_mem_half_page(src_page, dst_page, num_calls, config);
+
+    BOOT_SERVICES.free_pages(src_page, 1).map_err(|e| BenchError::cleanup("Failed to free source page", e))?;
+    BOOT_SERVICES.free_pages(dst_page, 1).map_err(|e| BenchError::cleanup("Failed to free destination page", e))?;
+    Ok(stats)
+}
+
+/// Times `CopyMem` of `HALF_PAGE_SIZE` bytes from `src` to `dst`, which must not overlap.
+fn copy_mem_half_page(src: usize, dst: usize, num_calls: usize, config: &BenchConfig) -> Measurements {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: Both addresses point to `HALF_PAGE_SIZE` bytes of allocated pages owned by the caller,
+        // and the ranges do not overlap.
+        unsafe { BOOT_SERVICES.copy_mem_unchecked(dst as *mut c_void, src as *const c_void, HALF_PAGE_SIZE) };
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    stats
+}
+
+/// Benchmarks copying a 4 KiB buffer with `core::ptr::copy_nonoverlapping`, bypassing the firmware.
+/// Comparing with `bench_copy_mem_4k` shows the overhead of calling through the boot services table.
+pub(crate) fn bench_rust_copy_mem(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let src: [u8; UEFI_PAGE_SIZE] = [0x5A; UEFI_PAGE_SIZE];
+    let mut dst: [u8; UEFI_PAGE_SIZE] = [0; UEFI_PAGE_SIZE];
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: Both buffers are valid for `UEFI_PAGE_SIZE` bytes and do not overlap.
+        // `black_box` keeps the compiler from eliding the repeated copy.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                core::hint::black_box(src.as_ptr()),
+                core::hint::black_box(dst.as_mut_ptr()),
+                UEFI_PAGE_SIZE,
+            )
+        };
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    core::hint::black_box(&dst);
+    Ok(stats)
+}
+
 /// Benchmarks memory initialization performance.
-pub(crate) fn bench_set_mem(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
+pub(crate) fn bench_set_mem(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     let mut dst: [u8; 128] = [0; 128];
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         BOOT_SERVICES.set_mem(&mut dst, 1);
@@ -111,14 +538,222 @@ pub(crate) fn bench_set_mem(_handle: efi::Handle, num_calls: usize) -> Result<St
     Ok(stats)
 }
 
-/// Benchmarks system memory map retrieval.
-pub(crate) fn bench_get_memory_map(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+/// Benchmarks memory filling on a full, page-aligned page from `allocate_pages`.
+/// Some firmware `SetMem` implementations have a fast path for page-aligned, page-sized buffers.
+pub(crate) fn bench_set_mem_page_aligned(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let page = BOOT_SERVICES
+        .allocate_pages(boot_services::allocation::AllocType::AnyPage, EfiMemoryType::BootServicesData, 1)
+        .map_err(|e| BenchError::setup("Failed to allocate pages", e))?;
+    // SAFETY: The page was just allocated and is exclusively owned by this benchmark until it is freed below.
+    let dst = unsafe { core::slice::from_raw_parts_mut(page as *mut u8, UEFI_PAGE_SIZE) };
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.set_mem(dst, 1);
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    BOOT_SERVICES.free_pages(page, 1).map_err(|e| BenchError::cleanup("Failed to free pages", e))?;
+    Ok(stats)
+}
+
+/// Benchmarks filling a buffer with `core::ptr::write_bytes`, bypassing the firmware.
+/// Comparing with `bench_set_mem` shows the overhead of calling through the boot services table.
+pub(crate) fn bench_rust_set_mem(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut dst: [u8; 128] = [0; 128];
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: The buffer is valid for its full length.
+        // `black_box` keeps the compiler from eliding the repeated fill.
+        unsafe { core::ptr::write_bytes(core::hint::black_box(dst.as_mut_ptr()), 1, dst.len()) };
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    core::hint::black_box(&dst);
+    Ok(stats)
+}
+
+/// Benchmarks system memory map retrieval, including the free of the returned map buffer.
+/// The number of `BootServicesData` pages in each map is recorded as well, which should stay constant across calls.
+/// The descriptor version of each map is also checked against the one defined by the UEFI specification and against
+/// the previous call, and a warning is logged the first time it differs.
+pub(crate) fn bench_get_memory_map(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    let mut previous_version: Option<u32> = None;
+    let mut version_warned = false;
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let memory_map =
+            BOOT_SERVICES.get_memory_map().map_err(|e| BenchError::measure("Failed to get memory map", e.0))?;
+        let retrieved = Arch::cpu_count();
+
+        // Some older implementations return a stale descriptor version.
+        let version = memory_map.descriptor_version;
+        if !version_warned
+            && (version != efi::MEMORY_DESCRIPTOR_VERSION
+                || previous_version.is_some_and(|previous| previous != version))
+        {
+            log::warn!(
+                "Memory map descriptor version {} is inconsistent (expected {}, previous call returned {:?}).",
+                version,
+                efi::MEMORY_DESCRIPTOR_VERSION,
+                previous_version
+            );
+            version_warned = true;
+        }
+        previous_version = Some(version);
+
+        // A growing page count across iterations points to a leak in the memory map path.
+        let boot_services_data_pages: u64 = memory_map
+            .descriptors
+            .iter()
+            .filter(|descriptor| descriptor.r#type == efi::BOOT_SERVICES_DATA)
+            .map(|descriptor| descriptor.number_of_pages)
+            .sum();
+        stats.update_aux("BootServicesData pages", boot_services_data_pages as f64);
+
+        // Freeing the map buffer is part of the measured cost, but the checks above are not.
+        let free_start = Arch::cpu_count();
+        drop(memory_map);
+        let end = Arch::cpu_count();
+        stats.update(((retrieved - start) + (end - free_start)) as f64);
+    }
+    Ok(stats)
+}
+
+/// Benchmarks memory map retrieval right after page allocations and frees.
+/// Firmware that eagerly merges freed regions changes the map between calls, which repeated calls alone do not show.
+pub(crate) fn bench_get_memory_map_after_free(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    const BLOCK_PAGES: usize = 8;
+    const BLOCK_COUNT: usize = 8;
+
+    // Allocate and free a set of blocks so later frees have neighboring free regions to merge with.
+    let mut blocks = [0usize; BLOCK_COUNT];
+    for block in blocks.iter_mut() {
+        *block = BOOT_SERVICES
+            .allocate_pages(boot_services::allocation::AllocType::AnyPage, EfiMemoryType::BootServicesData, BLOCK_PAGES)
+            .map_err(|e| BenchError::setup("Failed to allocate pages", e))?;
+    }
+    for block in blocks {
+        BOOT_SERVICES.free_pages(block, BLOCK_PAGES).map_err(|e| BenchError::setup("Failed to free pages", e))?;
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    let mut pending: Option<usize> = None;
+    for _ in 0..num_calls {
+        // Alternate between allocating and freeing a block so every other map reflects a merged free region.
+        pending = match pending.take() {
+            None => Some(
+                BOOT_SERVICES
+                    .allocate_pages(
+                        boot_services::allocation::AllocType::AnyPage,
+                        EfiMemoryType::BootServicesData,
+                        BLOCK_PAGES,
+                    )
+                    .map_err(|e| BenchError::setup("Failed to allocate pages", e))?,
+            ),
+            Some(block) => {
+                BOOT_SERVICES
+                    .free_pages(block, BLOCK_PAGES)
+                    .map_err(|e| BenchError::setup("Failed to free pages", e))?;
+                None
+            }
+        };
+
+        let start = Arch::cpu_count();
+        BOOT_SERVICES.get_memory_map().map_err(|e| BenchError::measure("Failed to get memory map", e.0))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    if let Some(block) = pending {
+        BOOT_SERVICES.free_pages(block, BLOCK_PAGES).map_err(|e| BenchError::cleanup("Failed to free pages", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks memory map retrieval after a page allocation and again after freeing the page.
+/// The map key must change with the free. The post-free call is reported next to the cycles of the post-allocation
+/// call, so firmware that caches the map between calls shows up as a cheaper post-free call.
+pub(crate) fn bench_get_memory_map_key_change(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
+        let pages = BOOT_SERVICES
+            .allocate_pages(boot_services::allocation::AllocType::AnyPage, EfiMemoryType::BootServicesData, 1)
+            .map_err(|e| BenchError::setup("Failed to allocate pages", e))?;
+
         let start = Arch::cpu_count();
-        BOOT_SERVICES.get_memory_map().map_err(|e| BenchError::BenchTest("Failed to get memory map", e.0))?;
+        let allocated_map_key = BOOT_SERVICES
+            .get_memory_map()
+            .map_err(|e| BenchError::measure("Failed to get memory map after allocation", e.0))?
+            .map_key;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
+
+        BOOT_SERVICES.free_pages(pages, 1).map_err(|e| BenchError::setup("Failed to free pages", e))?;
+
+        let start = Arch::cpu_count();
+        let freed_map_key = BOOT_SERVICES
+            .get_memory_map()
+            .map_err(|e| BenchError::measure("Failed to get memory map after free", e.0))?
+            .map_key;
+        let end = Arch::cpu_count();
+        stats.update_aux("Post-free cycles", (end - start) as f64);
+
+        // Both calls succeeded, so report the unchanged key as a firmware error rather than with their status.
+        if freed_map_key == allocated_map_key {
+            return Err(BenchError::measure(
+                "Memory map key did not change after freeing pages",
+                efi::Status::DEVICE_ERROR,
+            ));
+        }
+    }
+    Ok(stats)
+}
+
+/// Benchmarks a pool allocation immediately followed by a memory map retrieval, timed as one operation.
+/// OS loaders that keep a live memory map while allocating pay this combined cost for every allocation.
+pub(crate) fn bench_alloc_pool_then_get_memory_map(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let pool = BOOT_SERVICES
+            .allocate_pool(EfiMemoryType::BootServicesData, UEFI_PAGE_SIZE / 4)
+            .map_err(|e| BenchError::measure("Failed to allocate pool", e))?;
+        let memory_map =
+            BOOT_SERVICES.get_memory_map().map_err(|e| BenchError::measure("Failed to get memory map", e.0))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        drop(memory_map);
+        BOOT_SERVICES.free_pool(pool).map_err(|e| BenchError::cleanup("Failed to free pool", e))?;
     }
     Ok(stats)
 }
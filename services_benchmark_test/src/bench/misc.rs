@@ -5,24 +5,104 @@
 //! SPDX-License-Identifier: Apache-2.0
 //!
 
+use crate::alloc::vec;
+
 use core::ffi::c_void;
 
 use mu_rust_helpers::perf_timer::{Arch, ArchFunctionality as _};
 use patina::boot_services::BootServices as _;
 use r_efi::efi;
-use rolling_stats::Stats;
 
-use crate::{BOOT_SERVICES, bench::TEST_GUID1, error::BenchError};
+use crate::{BOOT_SERVICES, bench::TEST_GUID1, config::BenchConfig, error::BenchError, measure::Measurements};
 
 /// Benchmarks checksum calculation performance.
-pub(crate) fn bench_calculate_crc32(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    // The actual data values do not affect CRC32 performance, so use all zeroes for simplicity.
+pub(crate) fn bench_calculate_crc32(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Table-driven CRC32 performance does not depend on the data values, so use all zeroes as the baseline.
     let data: [u8; 128] = [0; 128];
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         let _crc =
-            BOOT_SERVICES.calculate_crc_32(&data).map_err(|e| BenchError::BenchTest("Failed to calculate CRC32", e))?;
+            BOOT_SERVICES.calculate_crc_32(&data).map_err(|e| BenchError::measure("Failed to calculate CRC32", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    Ok(stats)
+}
+
+/// Smallest and largest buffer sizes, as powers of two, measured by `bench_crc32_scaling`.
+const CRC32_SCALING_MIN_SHIFT: u32 = 4;
+const CRC32_SCALING_MAX_SHIFT: u32 = 16;
+
+/// Benchmarks checksum calculation over buffers from 16 bytes to 64 KiB, doubling in size.
+/// Each size is reported as a row of the data series, which gives the CRC32 scaling curve of the firmware. The
+/// benchmark's own row holds the cycles of a full sweep over all sizes.
+pub(crate) fn bench_crc32_scaling(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let data = vec![0u8; 1 << CRC32_SCALING_MAX_SHIFT];
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let mut sweep_cycles = 0.0;
+        for shift in CRC32_SCALING_MIN_SHIFT..=CRC32_SCALING_MAX_SHIFT {
+            let size = 1 << shift;
+            let start = Arch::cpu_count();
+            // SAFETY: `data` is valid for reads of `size` bytes, as `size` is at most its length.
+            let _crc = unsafe { BOOT_SERVICES.calculate_crc_32_unchecked(data.as_ptr() as *const c_void, size) }
+                .map_err(|e| BenchError::measure("Failed to calculate CRC32", e))?;
+            let end = Arch::cpu_count();
+            stats.update_series("bytes", size, (end - start) as f64);
+            sweep_cycles += (end - start) as f64;
+        }
+        stats.update(sweep_cycles);
+    }
+    Ok(stats)
+}
+
+/// Benchmarks checksum calculation over a buffer of all `0xFF` bytes.
+/// Table-driven CRC32 is independent of the data, but hardware-accelerated implementations may not be, which comparing
+/// with the all-zeroes `bench_calculate_crc32` shows.
+pub(crate) fn bench_calculate_crc32_all_ones(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let data: [u8; 128] = [0xFF; 128];
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let _crc =
+            BOOT_SERVICES.calculate_crc_32(&data).map_err(|e| BenchError::measure("Failed to calculate CRC32", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    Ok(stats)
+}
+
+/// Benchmarks checksum calculation over a buffer split into four chunks, one call per chunk.
+/// Compared with `bench_calculate_crc32`, this separates the per-call overhead from the per-byte cost.
+pub(crate) fn bench_calculate_crc32_4chunks(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // The actual data values do not affect CRC32 performance, so use all zeroes for simplicity.
+    // The 128-byte buffer is laid out as four contiguous 32-byte chunks.
+    let data: [[u8; 32]; 4] = [[0; 32]; 4];
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        for chunk in &data {
+            let _crc = BOOT_SERVICES
+                .calculate_crc_32(chunk)
+                .map_err(|e| BenchError::measure("Failed to calculate CRC32", e))?;
+        }
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
     }
@@ -33,9 +113,10 @@ pub(crate) fn bench_calculate_crc32(_handle: efi::Handle, num_calls: usize) -> R
 pub(crate) fn bench_install_configuration_table(
     _handle: efi::Handle,
     num_calls: usize,
-) -> Result<Stats<f64>, BenchError> {
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     let table: u64 = 0xDEADBEEF;
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         // SAFETY: The test configuration table has no specific layout requirements.
@@ -44,7 +125,7 @@ pub(crate) fn bench_install_configuration_table(
             // installing a table with a duplicate GUID simply overwrites the previous entry.
             BOOT_SERVICES
                 .install_configuration_table(&TEST_GUID1, &table as *const u64 as *mut c_void)
-                .map_err(|e| BenchError::BenchTest("Failed to install configuration table", e))?;
+                .map_err(|e| BenchError::measure("Failed to install configuration table", e))?;
         }
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
@@ -54,7 +135,92 @@ pub(crate) fn bench_install_configuration_table(
     (unsafe {
         BOOT_SERVICES
             .install_configuration_table(&TEST_GUID1, core::ptr::null_mut() as *const u64 as *mut c_void)
-            .map_err(|e| BenchError::BenchCleanup("Failed to remove configuration table", e))
+            .map_err(|e| BenchError::cleanup("Failed to remove configuration table", e))
     })?;
     Ok(stats)
 }
+
+/// Returns a distinct GUID for the `index`-th dummy configuration table.
+const fn dummy_table_guid(index: usize) -> efi::Guid {
+    efi::Guid::from_fields(0x7ab1e000 | index as u32, 0x2468, 0x1357, 0xa5, 0x5a, &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab])
+}
+
+/// Benchmarks installation of the `N`th configuration table.
+/// `N - 1` dummy tables with distinct GUIDs are installed in setup, so the measured call must scan them before appending.
+pub(crate) fn bench_install_configuration_table_nth<const N: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let table: u64 = 0xDEADBEEF;
+    for i in 0..N.saturating_sub(1) {
+        // SAFETY: The test configuration table has no specific layout requirements.
+        unsafe {
+            BOOT_SERVICES
+                .install_configuration_table(&dummy_table_guid(i), &table as *const u64 as *mut c_void)
+                .map_err(|e| BenchError::setup("Failed to install dummy configuration table", e))?;
+        }
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: The test configuration table has no specific layout requirements.
+        unsafe {
+            BOOT_SERVICES
+                .install_configuration_table(&TEST_GUID1, &table as *const u64 as *mut c_void)
+                .map_err(|e| BenchError::measure("Failed to install configuration table", e))?;
+        }
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Remove the table so the next iteration appends a new entry instead of overwriting the existing one.
+        // SAFETY: The test configuration table has no specific layout requirements.
+        (unsafe {
+            BOOT_SERVICES
+                .install_configuration_table(&TEST_GUID1, core::ptr::null_mut() as *const u64 as *mut c_void)
+                .map_err(|e| BenchError::cleanup("Failed to remove configuration table", e))
+        })?;
+    }
+
+    for i in 0..N.saturating_sub(1) {
+        // SAFETY: The test configuration table has no specific layout requirements.
+        (unsafe {
+            BOOT_SERVICES
+                .install_configuration_table(&dummy_table_guid(i), core::ptr::null_mut() as *const u64 as *mut c_void)
+                .map_err(|e| BenchError::cleanup("Failed to remove dummy configuration table", e))
+        })?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks removal of a configuration table by installing a NULL pointer for its GUID.
+/// The table is installed in setup before every iteration, so each measured call removes an existing entry.
+pub(crate) fn bench_remove_configuration_table(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let table: u64 = 0xDEADBEEF;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // SAFETY: The test configuration table has no specific layout requirements.
+        unsafe {
+            BOOT_SERVICES
+                .install_configuration_table(&TEST_GUID1, &table as *const u64 as *mut c_void)
+                .map_err(|e| BenchError::setup("Failed to install configuration table", e))?;
+        }
+
+        let start = Arch::cpu_count();
+        // Remove the table by passing a NULL pointer.
+        // SAFETY: The test configuration table has no specific layout requirements.
+        (unsafe {
+            BOOT_SERVICES
+                .install_configuration_table(&TEST_GUID1, core::ptr::null_mut() as *const u64 as *mut c_void)
+                .map_err(|e| BenchError::measure("Failed to remove configuration table", e))
+        })?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    Ok(stats)
+}
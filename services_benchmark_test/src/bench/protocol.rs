@@ -8,52 +8,323 @@
 use core::ffi::c_void;
 
 use mu_rust_helpers::perf_timer::{Arch, ArchFunctionality as _};
-use patina::boot_services::{BootServices, event::EventType, tpl::Tpl};
+use patina::boot_services::{BootServices, event::EventType, protocol_handler::HandleSearchType, tpl::Tpl};
 use r_efi::efi;
-use rolling_stats::Stats;
 
 use crate::{
     BOOT_SERVICES,
-    bench::{TEST_GUID1, TestProtocol1},
+    bench::{
+        AcpiDevicePathNode, END_DEVICE_PATH_NODE, Install, MANY_GUIDS, PciDevicePathNode, TEST_GUID1, TEST_GUID2,
+        TestDataProtocol, TestProtocol1, TestProtocol2, efi_boot_services_table,
+    },
+    config::BenchConfig,
     error::BenchError,
+    measure::Measurements,
 };
 
-use crate::alloc::boxed::Box;
+use crate::alloc::{boxed::Box, vec, vec::Vec};
+
+/// Mock driver that consumes `TestProtocol1` on a controller by opening it `BY_DRIVER` in `Start`
+/// and closing it in `Stop`, like a real UEFI driver.
+extern "efiapi" fn consumer_supported(
+    this: *mut efi::protocols::driver_binding::Protocol,
+    controller_handle: efi::Handle,
+    _remaining_device_path: *mut efi::protocols::device_path::Protocol,
+) -> efi::Status {
+    // SAFETY: `this` is the driver binding installed by the benchmark.
+    let agent = unsafe { (*this).driver_binding_handle };
+    // SAFETY: The resulting interface reference is not used.
+    match unsafe {
+        BOOT_SERVICES.open_protocol::<TestProtocol1>(
+            controller_handle,
+            agent,
+            controller_handle,
+            efi::OPEN_PROTOCOL_BY_DRIVER,
+        )
+    } {
+        Ok(_) => match BOOT_SERVICES.close_protocol(controller_handle, &TEST_GUID1, agent, controller_handle) {
+            Ok(()) => efi::Status::SUCCESS,
+            Err(e) => e,
+        },
+        Err(e) => e,
+    }
+}
+
+extern "efiapi" fn consumer_start(
+    this: *mut efi::protocols::driver_binding::Protocol,
+    controller_handle: efi::Handle,
+    _remaining_device_path: *mut efi::protocols::device_path::Protocol,
+) -> efi::Status {
+    // SAFETY: `this` is the driver binding installed by the benchmark.
+    let agent = unsafe { (*this).driver_binding_handle };
+    // SAFETY: The resulting interface reference is not used.
+    match unsafe {
+        BOOT_SERVICES.open_protocol::<TestProtocol1>(
+            controller_handle,
+            agent,
+            controller_handle,
+            efi::OPEN_PROTOCOL_BY_DRIVER,
+        )
+    } {
+        Ok(_) => efi::Status::SUCCESS,
+        Err(e) => e,
+    }
+}
+
+extern "efiapi" fn consumer_stop(
+    this: *mut efi::protocols::driver_binding::Protocol,
+    controller_handle: efi::Handle,
+    _num_children: usize,
+    _child_handle_buffer: *mut efi::Handle,
+) -> efi::Status {
+    // SAFETY: `this` is the driver binding installed by the benchmark.
+    let agent = unsafe { (*this).driver_binding_handle };
+    match BOOT_SERVICES.close_protocol(controller_handle, &TEST_GUID1, agent, controller_handle) {
+        Ok(()) => efi::Status::SUCCESS,
+        Err(e) => e,
+    }
+}
+
+/// Opens the `TestProtocol1` instance on `handle` with `OPEN_PROTOCOL_GET_PROTOCOL` from `count` distinct agent handles.
+/// Each open adds an entry to the protocol's open list. Returns the agent installs for [`detach_consumers`].
+fn attach_consumers(handle: efi::Handle, count: usize) -> Result<Vec<Install<TestProtocol2>>, BenchError> {
+    let mut agent_installs = Vec::with_capacity(count);
+    for _ in 0..count {
+        let agent_install = BOOT_SERVICES
+            .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+            .map_err(|e| BenchError::setup("Failed to install agent protocol", e))?;
+        agent_installs.push(agent_install);
+    }
+    reopen_consumers(handle, &agent_installs)?;
+    Ok(agent_installs)
+}
+
+/// Opens the `TestProtocol1` instance on `handle` with `OPEN_PROTOCOL_GET_PROTOCOL` from each agent handle.
+/// The firmware drops `GET_PROTOCOL` opens when the interface is reinstalled, so they must be reopened afterwards.
+fn reopen_consumers(handle: efi::Handle, agent_installs: &[Install<TestProtocol2>]) -> Result<(), BenchError> {
+    for agent_install in agent_installs {
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        unsafe {
+            BOOT_SERVICES
+                .open_protocol::<TestProtocol1>(
+                    handle,
+                    agent_install.0,
+                    core::ptr::null_mut(),
+                    efi::OPEN_PROTOCOL_GET_PROTOCOL,
+                )
+                .map_err(|e| BenchError::setup("Failed to open protocol", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Closes the opens made by [`attach_consumers`] and uninstalls the agent handles.
+/// Opens that the firmware already dropped (e.g. on a reinstall of the interface) are skipped.
+fn detach_consumers(handle: efi::Handle, agent_installs: Vec<Install<TestProtocol2>>) -> Result<(), BenchError> {
+    for agent_install in agent_installs {
+        match BOOT_SERVICES.close_protocol(handle, &TEST_GUID1, agent_install.0, core::ptr::null_mut()) {
+            Ok(()) | Err(efi::Status::NOT_FOUND) => {}
+            Err(e) => return Err(BenchError::cleanup("Failed to close protocol", e)),
+        }
+        BOOT_SERVICES
+            .uninstall_protocol_interface(agent_install.0, agent_install.1)
+            .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
+    }
+    Ok(())
+}
+
+/// Installs the first `count` of [`MANY_GUIDS`] with null interfaces on a new handle and returns the handle.
+fn install_null_protocols(count: usize) -> Result<efi::Handle, BenchError> {
+    // SAFETY: The protocols are installed with null interfaces, which are never dereferenced.
+    let handle =
+        unsafe { BOOT_SERVICES.install_protocol_interface_unchecked(None, &MANY_GUIDS[0], core::ptr::null_mut()) }
+            .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    for guid in &MANY_GUIDS[1..count] {
+        // SAFETY: The protocols are installed with null interfaces, which are never dereferenced.
+        unsafe { BOOT_SERVICES.install_protocol_interface_unchecked(Some(handle), guid, core::ptr::null_mut()) }
+            .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    }
+    Ok(handle)
+}
+
+/// Uninstalls the protocols installed by [`install_null_protocols`], which also frees the handle.
+fn uninstall_null_protocols(handle: efi::Handle, count: usize) -> Result<(), BenchError> {
+    for guid in &MANY_GUIDS[..count] {
+        // SAFETY: The protocols were installed with null interfaces by `install_null_protocols`.
+        unsafe { BOOT_SERVICES.uninstall_protocol_interface_unchecked(handle, guid, core::ptr::null_mut()) }
+            .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    }
+    Ok(())
+}
 
 /// Benchmarks protocol installation performance.
 pub(crate) fn bench_install_protocol_interface(
     _handle: efi::Handle,
     num_calls: usize,
-) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let protocol_install = BOOT_SERVICES
+            .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+            .map_err(|e| BenchError::measure("Failed to install protocol", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+        BOOT_SERVICES
+            .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+            .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    }
+    Ok(stats)
+}
+
+/// Benchmarks protocol installation on an existing handle.
+/// Installing on an existing handle appends to its protocol list instead of creating a new handle.
+pub(crate) fn bench_install_protocol_interface_existing_handle(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up the base handle that the measured protocol is installed on.
+    let base_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install base protocol", e))?;
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         let protocol_install = BOOT_SERVICES
+            .install_protocol_interface(Some(base_install.0), Box::new(TestProtocol2 {}))
+            .map_err(|e| BenchError::measure("Failed to install protocol on existing handle", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+        BOOT_SERVICES
+            .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+            .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    }
+
+    // Uninstall the base protocol, which also frees the handle.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(base_install.0, base_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall base protocol", e))?;
+    Ok(stats)
+}
+
+/// Benchmarks protocol installation on a handle value whose last protocol was just uninstalled.
+/// The firmware frees such a handle, so it must detect the stale value and reject the install.
+pub(crate) fn bench_install_protocol_recycle_handle(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // Create a handle and free it again by uninstalling its only protocol.
+        let base_install = BOOT_SERVICES
             .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-            .map_err(|e| BenchError::BenchTest("Failed to install protocol", e))?;
+            .map_err(|e| BenchError::setup("Failed to install base protocol", e))?;
+        BOOT_SERVICES
+            .uninstall_protocol_interface(base_install.0, base_install.1)
+            .map_err(|e| BenchError::setup("Failed to uninstall base protocol", e))?;
+
+        let start = Arch::cpu_count();
+        let result = BOOT_SERVICES.install_protocol_interface(Some(base_install.0), Box::new(TestProtocol2 {}));
+        let end = Arch::cpu_count();
+        match result {
+            Err(efi::Status::INVALID_PARAMETER) => stats.update((end - start) as f64),
+            Err(e) => return Err(BenchError::measure("Failed to install protocol on freed handle", e)),
+            Ok(protocol_install) => {
+                BOOT_SERVICES
+                    .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+                    .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+                return Err(BenchError::measure(
+                    "Install on freed handle unexpectedly succeeded",
+                    efi::Status::SUCCESS,
+                ));
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// Benchmarks protocol installation on a handle that already has `N` protocols installed.
+/// Firmware that keeps a handle's protocols in a sorted list or array pays an O(N) cost for each append.
+/// Every install also checks the new GUID against the `N` installed ones to reject duplicates.
+pub(crate) fn bench_install_protocol_on_n_protocol_handle<const N: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let handle = install_null_protocols(N)?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let protocol_install = BOOT_SERVICES
+            .install_protocol_interface(Some(handle), Box::new(TestProtocol1 {}))
+            .map_err(|e| BenchError::measure("Failed to install protocol on existing handle", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
         BOOT_SERVICES
             .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
-            .map_err(|e| BenchError::BenchCleanup("Failed to uninstall protocol", e))?;
+            .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    }
+
+    uninstall_null_protocols(handle, N)?;
+    Ok(stats)
+}
+
+/// Benchmarks the failure path of installing a protocol that is already installed on the handle.
+/// Detecting the duplicate GUID may require a scan of the handle's protocol list.
+pub(crate) fn bench_install_then_reinstall_same_guid(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let result = BOOT_SERVICES.install_protocol_interface(Some(protocol_install.0), Box::new(TestProtocol1 {}));
+        let end = Arch::cpu_count();
+
+        match result {
+            Err(efi::Status::INVALID_PARAMETER) => stats.update((end - start) as f64),
+            Err(e) => return Err(BenchError::measure("Duplicate install returned unexpected status", e)),
+            Ok(duplicate_install) => {
+                BOOT_SERVICES
+                    .uninstall_protocol_interface(duplicate_install.0, duplicate_install.1)
+                    .map_err(|e| BenchError::cleanup("Failed to uninstall duplicate protocol", e))?;
+                return Err(BenchError::measure("Duplicate install unexpectedly succeeded", efi::Status::SUCCESS));
+            }
+        }
     }
+
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
     Ok(stats)
 }
 
 /// Benchmarks protocol opening performance.
 /// This is the preferred method (over `handle_protocol`) for retrieving protocol interfaces in modern UEFI (2.0+).
-pub(crate) fn bench_open_protocol(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
+pub(crate) fn bench_open_protocol(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     // Set up and install the protocol to be opened.
     let agent_install = BOOT_SERVICES
         .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-        .map_err(|e| BenchError::BenchSetup("Failed to install agent protocol", e))?;
+        .map_err(|e| BenchError::setup("Failed to install agent protocol", e))?;
     let controller_install = BOOT_SERVICES
         .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-        .map_err(|e| BenchError::BenchSetup("Failed to install controller protocol", e))?;
+        .map_err(|e| BenchError::setup("Failed to install controller protocol", e))?;
     let protocol_install = BOOT_SERVICES
         .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-        .map_err(|e| BenchError::BenchSetup("Failed to install protocol", e))?;
-    let mut stats: Stats<f64> = Stats::new();
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         // SAFETY: The resulting interface reference is not used at all during the test.
@@ -65,217 +336,1649 @@ pub(crate) fn bench_open_protocol(_handle: efi::Handle, num_calls: usize) -> Res
                     controller_install.0,
                     efi::OPEN_PROTOCOL_BY_DRIVER,
                 )
-                .map_err(|e| BenchError::BenchTest("Failed to open protocol", e))
+                .map_err(|e| BenchError::measure("Failed to open protocol", e))
         })?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
 
         BOOT_SERVICES
             .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, controller_install.0)
-            .map_err(|e| BenchError::BenchCleanup("Failed to close protocol", e))?;
+            .map_err(|e| BenchError::cleanup("Failed to close protocol", e))?;
     }
 
     // Uninstall mock protocols after benchmarking.
     BOOT_SERVICES
         .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall protocol", e))?;
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
     BOOT_SERVICES
         .uninstall_protocol_interface(agent_install.0, agent_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall agent protocol", e))?;
+        .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
     BOOT_SERVICES
         .uninstall_protocol_interface(controller_install.0, controller_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall controller protocol", e))?;
+        .map_err(|e| BenchError::cleanup("Failed to uninstall controller protocol", e))?;
 
     Ok(stats)
 }
 
-/// Benchmarks protocol closing performance.
-pub(crate) fn bench_close_protocol(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    // Set up and install the necessary protocol.
+/// Benchmarks a `GET_PROTOCOL` open of a protocol that the same agent closed just before the timed call.
+/// Compared against `open_protocol_warm`, this shows whether the firmware caches recently opened protocols.
+pub(crate) fn bench_open_protocol_cold(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    open_protocol_get_protocol(num_calls, false, config)
+}
+
+/// Benchmarks a `GET_PROTOCOL` open of a protocol that the same agent already holds open.
+pub(crate) fn bench_open_protocol_warm(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    open_protocol_get_protocol(num_calls, true, config)
+}
+
+/// Times repeated `GET_PROTOCOL` opens by a single agent. If `held_open` is set, the agent already holds one open of
+/// the protocol at every timed open, otherwise the protocol is closed (outside the timed region) before every open.
+/// The open list never grows beyond two entries, because the opens are closed after every iteration.
+fn open_protocol_get_protocol(
+    num_calls: usize,
+    held_open: bool,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Opens the protocol from the agent handle with `GET_PROTOCOL`.
+    fn open_get_protocol(
+        protocol_handle: efi::Handle,
+        agent_handle: efi::Handle,
+    ) -> Result<&'static mut TestProtocol1, efi::Status> {
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        unsafe {
+            BOOT_SERVICES.open_protocol::<TestProtocol1>(
+                protocol_handle,
+                agent_handle,
+                core::ptr::null_mut(),
+                efi::OPEN_PROTOCOL_GET_PROTOCOL,
+            )
+        }
+    }
+
+    // Set up and install the protocol to be opened.
+    let agent_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install agent protocol", e))?;
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+
+    // Prime the open so that even the first timed open of the warm variant follows a previous open.
+    open_get_protocol(protocol_install.0, agent_install.0)
+        .map_err(|e| BenchError::setup("Failed to open protocol", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        if !held_open {
+            BOOT_SERVICES
+                .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, core::ptr::null_mut())
+                .map_err(|e| BenchError::setup("Failed to close protocol", e))?;
+        }
+
+        let start = Arch::cpu_count();
+        open_get_protocol(protocol_install.0, agent_install.0)
+            .map_err(|e| BenchError::measure("Failed to open protocol", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        if held_open {
+            // Closing removes every open held by the agent, so reopen once to restore the single held open.
+            BOOT_SERVICES
+                .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, core::ptr::null_mut())
+                .map_err(|e| BenchError::setup("Failed to close protocol", e))?;
+            open_get_protocol(protocol_install.0, agent_install.0)
+                .map_err(|e| BenchError::setup("Failed to open protocol", e))?;
+        }
+    }
+
+    // Close the open held by the agent and uninstall mock protocols after benchmarking.
+    BOOT_SERVICES
+        .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, core::ptr::null_mut())
+        .map_err(|e| BenchError::cleanup("Failed to close protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(agent_install.0, agent_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks a `BY_DRIVER` open of a handle's protocol right after the same handle's protocol was opened and closed.
+/// Compared against `open_protocol_cold_cache`, this detects a single-entry cache of the most recently opened protocol.
+pub(crate) fn bench_open_protocol_warm_cache(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    open_protocol_after_previous_open(num_calls, true, config)
+}
+
+/// Benchmarks a `BY_DRIVER` open of a handle's protocol right after a different handle's protocol was opened and closed.
+pub(crate) fn bench_open_protocol_cold_cache(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    open_protocol_after_previous_open(num_calls, false, config)
+}
+
+/// Times a `BY_DRIVER` open of a protocol that follows an untimed open and close of the same protocol on either the
+/// same handle (`same_handle`) or a different one.
+fn open_protocol_after_previous_open(
+    num_calls: usize,
+    same_handle: bool,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up the agent and controller handles, the measured protocol, and the protocol on the other handle.
     let agent_install = BOOT_SERVICES
         .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-        .map_err(|e| BenchError::BenchSetup("Failed install agent handle", e))?;
+        .map_err(|e| BenchError::setup("Failed to install agent protocol", e))?;
     let controller_install = BOOT_SERVICES
         .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-        .map_err(|e| BenchError::BenchSetup("Failed to install controller handle.", e))?;
+        .map_err(|e| BenchError::setup("Failed to install controller protocol", e))?;
     let protocol_install = BOOT_SERVICES
         .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-        .map_err(|e| BenchError::BenchSetup("Failed to install protocol handle", e))?;
-    let mut stats: Stats<f64> = Stats::new();
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    let other_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install other protocol", e))?;
+    let previous_handle = if same_handle { protocol_install.0 } else { other_install.0 };
+
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         // SAFETY: The resulting interface reference is not used at all during the test.
-        unsafe {
+        (unsafe {
             BOOT_SERVICES
                 .open_protocol::<TestProtocol1>(
-                    protocol_install.0,
+                    previous_handle,
                     agent_install.0,
                     controller_install.0,
                     efi::OPEN_PROTOCOL_BY_DRIVER,
                 )
-                .map_err(|e| BenchError::BenchSetup("Failed to open protocol", e))?;
-        }
+                .map_err(|e| BenchError::setup("Failed to open previous protocol", e))
+        })?;
+        BOOT_SERVICES
+            .close_protocol(previous_handle, &TEST_GUID1, agent_install.0, controller_install.0)
+            .map_err(|e| BenchError::setup("Failed to close previous protocol", e))?;
 
         let start = Arch::cpu_count();
-        BOOT_SERVICES
-            .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, controller_install.0)
-            .map_err(|e| BenchError::BenchTest("Failed to close protocol", e))?;
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        (unsafe {
+            BOOT_SERVICES
+                .open_protocol::<TestProtocol1>(
+                    protocol_install.0,
+                    agent_install.0,
+                    controller_install.0,
+                    efi::OPEN_PROTOCOL_BY_DRIVER,
+                )
+                .map_err(|e| BenchError::measure("Failed to open protocol", e))
+        })?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
+
+        BOOT_SERVICES
+            .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, controller_install.0)
+            .map_err(|e| BenchError::cleanup("Failed to close protocol", e))?;
     }
 
     // Uninstall mock protocols after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(other_install.0, other_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall other protocol", e))?;
     BOOT_SERVICES
         .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall protocol", e))?;
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
     BOOT_SERVICES
         .uninstall_protocol_interface(agent_install.0, agent_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall agent protocol", e))?;
+        .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
     BOOT_SERVICES
         .uninstall_protocol_interface(controller_install.0, controller_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall controller protocol", e))?;
+        .map_err(|e| BenchError::cleanup("Failed to uninstall controller protocol", e))?;
 
     Ok(stats)
 }
 
-/// Benchmarks protocol handling performance.
-/// This is a legacy method but is still included due to needing to support legacy UEFI (1.0).
-pub(crate) fn bench_handle_protocol(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    // Set up and install the protocol to be accessed.
-    let protocol_install = BOOT_SERVICES
+/// Benchmarks protocol opening with `OPEN_PROTOCOL_BY_CHILD_CONTROLLER`, as bus drivers do for each child they create.
+/// The bus driver's image handle opens the controller's protocol on behalf of a child handle.
+pub(crate) fn bench_open_protocol_by_child_controller(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up the image, controller, and child handles.
+    let image_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install image protocol", e))?;
+    let controller_install = BOOT_SERVICES
         .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-        .map_err(|e| BenchError::BenchSetup("Failed to install protocol", e))?;
-    let mut stats: Stats<f64> = Stats::new();
+        .map_err(|e| BenchError::setup("Failed to install controller protocol", e))?;
+    let child_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install child protocol", e))?;
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         // SAFETY: The resulting interface reference is not used at all during the test.
         (unsafe {
             BOOT_SERVICES
-                .handle_protocol::<TestProtocol1>(protocol_install.0)
-                .map_err(|e| BenchError::BenchTest("Failed to handle protocol", e))
+                .open_protocol::<TestProtocol1>(
+                    controller_install.0,
+                    image_install.0,
+                    child_install.0,
+                    efi::OPEN_PROTOCOL_BY_CHILD_CONTROLLER,
+                )
+                .map_err(|e| BenchError::measure("Failed to open protocol by child controller", e))
         })?;
-
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
+
+        // Close the open entry for the child, as a bus driver does when destroying the child.
+        BOOT_SERVICES
+            .close_protocol(controller_install.0, &TEST_GUID1, image_install.0, child_install.0)
+            .map_err(|e| BenchError::cleanup("Failed to close protocol by child controller", e))?;
     }
-    // Uninstall mock protocol after benchmarking.
+
+    // Uninstall mock protocols after benchmarking.
     BOOT_SERVICES
-        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall protocol", e))?;
+        .uninstall_protocol_interface(child_install.0, child_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall child protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(controller_install.0, controller_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall controller protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(image_install.0, image_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall image protocol", e))?;
+
     Ok(stats)
 }
 
-/// Benchmarks device path resolution.
-pub(crate) fn bench_locate_device_path(handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    // Find existing protocol handles to locate device path.
-    // SAFETY: There is only one reference to the `loaded_image_protocol` interface.
-    let loaded_image_protocol = unsafe {
-        BOOT_SERVICES
-            .handle_protocol::<efi::protocols::loaded_image::Protocol>(handle)
-            .map_err(|e| BenchError::BenchSetup("Failed to get loaded image protocol.", e))?
-    };
-    // SAFETY: There is only one reference to the `device_path_protocol` interface.
-    let device_path_protocol = unsafe {
-        BOOT_SERVICES
-            .handle_protocol::<efi::protocols::device_path::Protocol>(loaded_image_protocol.device_handle)
-            .map_err(|e| BenchError::BenchSetup("Failed to device path protocol.", e))?
-    };
+/// Benchmarks enumerating the protocols on the image handle and opening each of them, as device manager-style code does.
+/// The number of protocols depends on the firmware, so it is reported next to the cycle counts.
+///
+/// The opens use a dedicated agent handle, so closing them does not remove the firmware's own opens on the image handle.
+pub(crate) fn bench_open_all_protocols_on_image_handle(
+    handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let agent_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install agent protocol", e))?;
 
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
-        let mut device_path_ptr = device_path_protocol as *mut efi::protocols::device_path::Protocol;
         let start = Arch::cpu_count();
-        // SAFETY: The device path has been constructed above as a valid pointer.
-        unsafe {
-            BOOT_SERVICES
-                .locate_device_path(&efi::protocols::device_path::PROTOCOL_GUID, &mut device_path_ptr as *mut _)
-                .map_err(|e| BenchError::BenchTest("Failed to locate device path", e))
-        }?;
+        let protocols = BOOT_SERVICES
+            .protocols_per_handle(handle)
+            .map_err(|e| BenchError::measure("Failed to get protocols per handle", e))?;
+        for protocol in protocols.iter() {
+            // SAFETY: The resulting interface pointer is not dereferenced.
+            unsafe {
+                BOOT_SERVICES
+                    .open_protocol_unchecked(
+                        handle,
+                        protocol,
+                        agent_install.0,
+                        core::ptr::null_mut(),
+                        efi::OPEN_PROTOCOL_GET_PROTOCOL,
+                    )
+                    .map_err(|e| BenchError::measure("Failed to open protocol", e))?;
+            }
+        }
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
+        stats.update_aux("Protocols opened", protocols.len() as f64);
+
+        // Remove the benchmark's open entries so they do not pile up across iterations.
+        for protocol in protocols.iter() {
+            BOOT_SERVICES
+                .close_protocol(handle, protocol, agent_install.0, core::ptr::null_mut())
+                .map_err(|e| BenchError::cleanup("Failed to close protocol", e))?;
+        }
     }
 
+    BOOT_SERVICES
+        .uninstall_protocol_interface(agent_install.0, agent_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
+
     Ok(stats)
 }
 
-/// Benchmarks protocol metadata retrieval.
-pub(crate) fn bench_open_protocol_information(handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+/// Benchmarks protocol opening when `N` other agents already have the protocol open.
+/// Each open appends an entry to the protocol's consumer tracking list, so this shows how the list size affects latency.
+///
+/// UEFI only allows a single `BY_DRIVER` open per protocol, so the `N` existing consumers hold a `GET_PROTOCOL` open
+/// while the measured open is `BY_DRIVER` as in `open_protocol`.
+pub(crate) fn bench_open_protocol_nth_consumer<const N: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let agent_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install agent protocol", e))?;
+    let controller_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install controller protocol", e))?;
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+
+    // Attach the existing consumers, each with a distinct agent handle.
+    let consumer_installs = attach_consumers(protocol_install.0, N)?;
+
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
-        let _info = BOOT_SERVICES
-            .open_protocol_information(handle, &efi::protocols::loaded_image::PROTOCOL_GUID)
-            .map_err(|e| BenchError::BenchTest("Failed to get open protocol information", e))?;
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        (unsafe {
+            BOOT_SERVICES
+                .open_protocol::<TestProtocol1>(
+                    protocol_install.0,
+                    agent_install.0,
+                    controller_install.0,
+                    efi::OPEN_PROTOCOL_BY_DRIVER,
+                )
+                .map_err(|e| BenchError::measure("Failed to open protocol", e))
+        })?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
+
+        BOOT_SERVICES
+            .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, controller_install.0)
+            .map_err(|e| BenchError::cleanup("Failed to close protocol", e))?;
     }
 
+    // Detach the consumers and uninstall mock protocols after benchmarking.
+    detach_consumers(protocol_install.0, consumer_installs)?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(agent_install.0, agent_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(controller_install.0, controller_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall controller protocol", e))?;
+
     Ok(stats)
 }
 
-/// Benchmarks handle protocol enumeration.
-pub(crate) fn bench_protocols_per_handle(handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
-    for _ in 0..num_calls {
-        let start = Arch::cpu_count();
-        let _protocols = BOOT_SERVICES
+/// Benchmarks the combined cost of a protocol open and the matching close.
+/// This is the relevant metric for drivers that open, use, and close a protocol within a single function.
+pub(crate) fn bench_open_close_roundtrip(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up and install the protocol to be opened.
+    let agent_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install agent protocol", e))?;
+    let controller_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install controller protocol", e))?;
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        (unsafe {
+            BOOT_SERVICES
+                .open_protocol::<TestProtocol1>(
+                    protocol_install.0,
+                    agent_install.0,
+                    controller_install.0,
+                    efi::OPEN_PROTOCOL_BY_DRIVER,
+                )
+                .map_err(|e| BenchError::measure("Failed to open protocol", e))
+        })?;
+        BOOT_SERVICES
+            .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, controller_install.0)
+            .map_err(|e| BenchError::measure("Failed to close protocol", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    // Uninstall mock protocols after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(agent_install.0, agent_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(controller_install.0, controller_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall controller protocol", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks a protocol open and close pair performed at `TPL_CALLBACK`.
+/// Protocol database changes at an elevated TPL may skip notification dispatch, which this quantifies against
+/// `open_protocol`.
+pub(crate) fn bench_open_close_protocol_at_tpl_callback(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up and install the protocol to be opened.
+    let agent_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install agent protocol", e))?;
+    let controller_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install controller protocol", e))?;
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let old_tpl = BOOT_SERVICES.raise_tpl(Tpl::CALLBACK);
+
+        let start = Arch::cpu_count();
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        let open_result = unsafe {
+            BOOT_SERVICES.open_protocol::<TestProtocol1>(
+                protocol_install.0,
+                agent_install.0,
+                controller_install.0,
+                efi::OPEN_PROTOCOL_BY_DRIVER,
+            )
+        };
+        let close_result =
+            BOOT_SERVICES.close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, controller_install.0);
+        let end = Arch::cpu_count();
+
+        // Restore the TPL before reporting any failure.
+        BOOT_SERVICES.restore_tpl(old_tpl);
+        open_result.map_err(|e| BenchError::measure("Failed to open protocol", e))?;
+        close_result.map_err(|e| BenchError::measure("Failed to close protocol", e))?;
+        stats.update((end - start) as f64);
+    }
+
+    // Uninstall mock protocols after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(agent_install.0, agent_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(controller_install.0, controller_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall controller protocol", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks exclusive protocol opening performance.
+/// `OPEN_PROTOCOL_EXCLUSIVE` claims exclusive ownership, so the firmware must check all existing open entries.
+pub(crate) fn bench_open_protocol_exclusive(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up and install the protocol to be opened, along with two competing agents.
+    let agent_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install agent protocol", e))?;
+    let other_agent_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install other agent protocol", e))?;
+    let controller_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install controller protocol", e))?;
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+
+    // Validate that an exclusive open denies access to other agents (not measured).
+    // SAFETY: The resulting interface reference is not used at all during the test.
+    unsafe {
+        BOOT_SERVICES
+            .open_protocol::<TestProtocol1>(
+                protocol_install.0,
+                agent_install.0,
+                controller_install.0,
+                efi::OPEN_PROTOCOL_EXCLUSIVE,
+            )
+            .map_err(|e| BenchError::setup("Failed to open protocol exclusively", e))?;
+    }
+    // SAFETY: The resulting interface reference is not used at all during the test.
+    match unsafe {
+        BOOT_SERVICES.open_protocol::<TestProtocol1>(
+            protocol_install.0,
+            other_agent_install.0,
+            controller_install.0,
+            efi::OPEN_PROTOCOL_EXCLUSIVE,
+        )
+    } {
+        Err(efi::Status::ACCESS_DENIED) => (),
+        Err(e) => return Err(BenchError::setup("Second exclusive open returned unexpected status", e)),
+        Ok(_) => {
+            return Err(BenchError::setup("Second exclusive open was not denied", efi::Status::SUCCESS));
+        }
+    }
+    BOOT_SERVICES
+        .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, controller_install.0)
+        .map_err(|e| BenchError::setup("Failed to close protocol", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        (unsafe {
+            BOOT_SERVICES
+                .open_protocol::<TestProtocol1>(
+                    protocol_install.0,
+                    agent_install.0,
+                    controller_install.0,
+                    efi::OPEN_PROTOCOL_EXCLUSIVE,
+                )
+                .map_err(|e| BenchError::measure("Failed to open protocol exclusively", e))
+        })?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES
+            .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, controller_install.0)
+            .map_err(|e| BenchError::cleanup("Failed to close protocol", e))?;
+    }
+
+    // Uninstall mock protocols after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(agent_install.0, agent_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(other_agent_install.0, other_agent_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall other agent protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(controller_install.0, controller_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall controller protocol", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol closing performance.
+pub(crate) fn bench_close_protocol(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up and install the necessary protocol.
+    let agent_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed install agent handle", e))?;
+    let controller_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install controller handle.", e))?;
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol handle", e))?;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        unsafe {
+            BOOT_SERVICES
+                .open_protocol::<TestProtocol1>(
+                    protocol_install.0,
+                    agent_install.0,
+                    controller_install.0,
+                    efi::OPEN_PROTOCOL_BY_DRIVER,
+                )
+                .map_err(|e| BenchError::setup("Failed to open protocol", e))?;
+        }
+
+        let start = Arch::cpu_count();
+        BOOT_SERVICES
+            .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, controller_install.0)
+            .map_err(|e| BenchError::measure("Failed to close protocol", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    // Uninstall mock protocols after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(agent_install.0, agent_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(controller_install.0, controller_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall controller protocol", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol closing performance after an `OPEN_PROTOCOL_GET_PROTOCOL` open.
+/// Unlike `BY_DRIVER`, a `GET_PROTOCOL` open does not have to be tracked, so closing it may be a near no-op.
+pub(crate) fn bench_close_protocol_get_protocol(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up and install the necessary protocol.
+    let agent_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed install agent handle", e))?;
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol handle", e))?;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        unsafe {
+            BOOT_SERVICES
+                .open_protocol::<TestProtocol1>(
+                    protocol_install.0,
+                    agent_install.0,
+                    core::ptr::null_mut(),
+                    efi::OPEN_PROTOCOL_GET_PROTOCOL,
+                )
+                .map_err(|e| BenchError::setup("Failed to open protocol", e))?;
+        }
+
+        let start = Arch::cpu_count();
+        BOOT_SERVICES
+            .close_protocol(protocol_install.0, &TEST_GUID1, agent_install.0, core::ptr::null_mut())
+            .map_err(|e| BenchError::measure("Failed to close protocol", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    // Uninstall mock protocols after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(agent_install.0, agent_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall agent protocol", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol handling performance.
+/// This is a legacy method but is still included due to needing to support legacy UEFI (1.0).
+pub(crate) fn bench_handle_protocol(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up and install the protocol to be accessed.
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        (unsafe {
+            BOOT_SERVICES
+                .handle_protocol::<TestProtocol1>(protocol_install.0)
+                .map_err(|e| BenchError::measure("Failed to handle protocol", e))
+        })?;
+
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+    // Uninstall mock protocol after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    Ok(stats)
+}
+
+/// Benchmarks `handle_protocol` for a protocol that is not installed on the queried handle.
+/// Compared against `bench_handle_protocol`, this shows how early the firmware exits on a miss.
+pub(crate) fn bench_handle_protocol_not_found(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up a handle with only `TestProtocol1` installed.
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: The query is expected to fail, so no interface reference is produced.
+        let result = unsafe { BOOT_SERVICES.handle_protocol::<TestProtocol2>(protocol_install.0) };
+        let end = Arch::cpu_count();
+        match result {
+            Err(efi::Status::UNSUPPORTED) => stats.update((end - start) as f64),
+            Err(e) => return Err(BenchError::measure("Failed to handle protocol", e)),
+            Ok(_) => {
+                return Err(BenchError::measure("Handle protocol unexpectedly succeeded", efi::Status::SUCCESS));
+            }
+        }
+    }
+    // Uninstall mock protocol after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    Ok(stats)
+}
+
+/// Benchmarks querying two protocols on the same handle back-to-back.
+/// The first query is reported as the main result and the second as a secondary statistic,
+/// which shows whether the second lookup benefits from the handle already being hot.
+pub(crate) fn bench_handle_protocol_multiple_on_same_handle(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Set up and install both protocols on the same handle.
+    let protocol1_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    let protocol2_install = BOOT_SERVICES
+        .install_protocol_interface(Some(protocol1_install.0), Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install second protocol", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        (unsafe {
+            BOOT_SERVICES
+                .handle_protocol::<TestProtocol1>(protocol1_install.0)
+                .map_err(|e| BenchError::measure("Failed to handle protocol", e))
+        })?;
+        let mid = Arch::cpu_count();
+        // SAFETY: The resulting interface reference is not used at all during the test.
+        (unsafe {
+            BOOT_SERVICES
+                .handle_protocol::<TestProtocol2>(protocol1_install.0)
+                .map_err(|e| BenchError::measure("Failed to handle second protocol", e))
+        })?;
+        let end = Arch::cpu_count();
+        stats.update((mid - start) as f64);
+        stats.update_aux("Second query cycles", (end - mid) as f64);
+    }
+
+    // Uninstall mock protocols after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol2_install.0, protocol2_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall second protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol1_install.0, protocol1_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    Ok(stats)
+}
+
+/// Benchmarks device path resolution.
+pub(crate) fn bench_locate_device_path(
+    handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Find existing protocol handles to locate device path.
+    // SAFETY: There is only one reference to the `loaded_image_protocol` interface.
+    let loaded_image_protocol = unsafe {
+        BOOT_SERVICES
+            .handle_protocol::<efi::protocols::loaded_image::Protocol>(handle)
+            .map_err(|e| BenchError::setup("Failed to get loaded image protocol.", e))?
+    };
+    // SAFETY: There is only one reference to the `device_path_protocol` interface.
+    let device_path_protocol = unsafe {
+        BOOT_SERVICES
+            .handle_protocol::<efi::protocols::device_path::Protocol>(loaded_image_protocol.device_handle)
+            .map_err(|e| BenchError::setup("Failed to device path protocol.", e))?
+    };
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let mut device_path_ptr = device_path_protocol as *mut efi::protocols::device_path::Protocol;
+        let start = Arch::cpu_count();
+        // SAFETY: The device path has been constructed above as a valid pointer.
+        unsafe {
+            BOOT_SERVICES
+                .locate_device_path(&efi::protocols::device_path::PROTOCOL_GUID, &mut device_path_ptr as *mut _)
+                .map_err(|e| BenchError::measure("Failed to locate device path", e))
+        }?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    Ok(stats)
+}
+
+/// A two-node device path of a PCI device (`PciRoot(uid)/Pci(device,function)`).
+#[repr(C, packed)]
+struct PciDevicePath {
+    pci_root: AcpiDevicePathNode,
+    pci: PciDevicePathNode,
+    end: efi::protocols::device_path::End,
+}
+
+/// Benchmarks device path resolution of a constructed multi-node device path (`PciRoot(0x0)/Pci(0x0,0x0)`).
+/// This is closer to how the boot manager finds block devices than the loaded image's device path.
+/// Platforms without a `PciRoot(0x0)` device, such as many AArch64 and virtual platforms, skip the benchmark.
+pub(crate) fn bench_locate_device_path_multi_node(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut device_path = PciDevicePath {
+        pci_root: AcpiDevicePathNode::pci_root(0),
+        pci: PciDevicePathNode::new(0, 0),
+        end: END_DEVICE_PATH_NODE,
+    };
+
+    // Resolve the path once before timing, so that a platform without a PCI root bridge is skipped instead of failing.
+    let mut device_path_ptr = &mut device_path as *mut PciDevicePath as *mut efi::protocols::device_path::Protocol;
+    // SAFETY: The device path has been constructed above as a valid, terminated device path.
+    match unsafe {
+        BOOT_SERVICES.locate_device_path(&efi::protocols::device_path::PROTOCOL_GUID, &mut device_path_ptr as *mut _)
+    } {
+        Ok(_) => (),
+        Err(efi::Status::NOT_FOUND) => return Err(BenchError::unsupported("No PciRoot(0) device on this platform")),
+        Err(e) => return Err(BenchError::setup("Failed to locate multi-node device path", e)),
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let mut device_path_ptr = &mut device_path as *mut PciDevicePath as *mut efi::protocols::device_path::Protocol;
+        let start = Arch::cpu_count();
+        // SAFETY: The device path has been constructed above as a valid, terminated device path.
+        unsafe {
+            BOOT_SERVICES
+                .locate_device_path(&efi::protocols::device_path::PROTOCOL_GUID, &mut device_path_ptr as *mut _)
+                .map_err(|e| BenchError::measure("Failed to locate multi-node device path", e))
+        }?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks device path resolution of a device path (`PciRoot(0xFF)/Pci(0x1F,0x7)`) that no handle's device path is
+/// a prefix of. This is the common case in early boot, before the device of a boot option has been connected, and
+/// requires a search of every handle with a device path.
+pub(crate) fn bench_locate_device_path_not_found(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut device_path = PciDevicePath {
+        pci_root: AcpiDevicePathNode::pci_root(0xFF),
+        pci: PciDevicePathNode::new(0x1F, 0x7),
+        end: END_DEVICE_PATH_NODE,
+    };
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let mut device_path_ptr = &mut device_path as *mut PciDevicePath as *mut efi::protocols::device_path::Protocol;
+        let start = Arch::cpu_count();
+        // SAFETY: The device path has been constructed above as a valid, terminated device path.
+        let result = unsafe {
+            BOOT_SERVICES
+                .locate_device_path(&efi::protocols::device_path::PROTOCOL_GUID, &mut device_path_ptr as *mut _)
+        };
+        let end = Arch::cpu_count();
+        match result {
+            Err(efi::Status::NOT_FOUND) => stats.update((end - start) as f64),
+            Err(e) => return Err(BenchError::measure("Failed to locate non-existent device path", e)),
+            Ok(_) => {
+                return Err(BenchError::measure(
+                    "Locating non-existent device path unexpectedly succeeded",
+                    efi::Status::SUCCESS,
+                ));
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks locating the first instance of a test protocol installed by the benchmark.
+pub(crate) fn bench_locate_protocol(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: The returned interface pointer is not dereferenced.
+        unsafe {
+            BOOT_SERVICES
+                .locate_protocol_unchecked(&TEST_GUID1, core::ptr::null_mut())
+                .map_err(|e| BenchError::measure("Failed to locate protocol", e))
+        }?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol lookup through a protocol notify registration key.
+/// With a key, the firmware returns the next handle queued for the registration instead of searching the database.
+pub(crate) fn bench_locate_protocol_by_key(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Mock notify does nothing.
+    extern "efiapi" fn mock_notify(_ptr: *mut c_void, _data: *mut i32) {}
+
+    // Register for `TestProtocol1` installations to get a search key.
+    let event = BOOT_SERVICES
+        .create_event(EventType::NOTIFY_SIGNAL, Tpl::NOTIFY, Some(mock_notify), &mut 0 as *mut i32)
+        .map_err(|e| BenchError::setup("Failed to create valid event", e))?;
+    let registration = BOOT_SERVICES
+        .register_protocol_notify(&TEST_GUID1, event)
+        .map_err(|e| BenchError::setup("Failed to register protocol notify", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // Each lookup consumes the queued handle, so install a new one for every iteration.
+        let protocol_install = BOOT_SERVICES
+            .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+            .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+
+        let start = Arch::cpu_count();
+        // SAFETY: The returned interface pointer is not dereferenced.
+        unsafe {
+            BOOT_SERVICES
+                .locate_protocol_unchecked(&TEST_GUID1, registration.as_ptr())
+                .map_err(|e| BenchError::measure("Failed to locate protocol by registration key", e))
+        }?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES
+            .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+            .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    }
+
+    // Closing the event also removes the notify registration.
+    BOOT_SERVICES.close_event(event).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks locating the loaded image protocol, which firmware always has installed.
+/// Comparing against `bench_locate_protocol` shows whether well-known GUIDs take a faster path than test GUIDs.
+pub(crate) fn bench_locate_protocol_builtin(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // SAFETY: The returned interface pointer is not dereferenced.
+        unsafe {
+            BOOT_SERVICES
+                .locate_protocol_unchecked(&efi::protocols::loaded_image::PROTOCOL_GUID, core::ptr::null_mut())
+                .map_err(|e| BenchError::measure("Failed to locate loaded image protocol", e))
+        }?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol metadata retrieval.
+pub(crate) fn bench_open_protocol_information(
+    handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let _info = BOOT_SERVICES
+            .open_protocol_information(handle, &efi::protocols::loaded_image::PROTOCOL_GUID)
+            .map_err(|e| BenchError::measure("Failed to get open protocol information", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol metadata retrieval together with freeing the returned buffer, as every caller has to.
+/// The query alone is reported next to the cycles, which separates the firmware's pool allocator cost from the query.
+pub(crate) fn bench_open_protocol_information_with_free(
+    handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let info = BOOT_SERVICES
+            .open_protocol_information(handle, &efi::protocols::loaded_image::PROTOCOL_GUID)
+            .map_err(|e| BenchError::measure("Failed to get open protocol information", e))?;
+        let query_end = Arch::cpu_count();
+        // Dropping the buffer returns it to the firmware with `free_pool`.
+        drop(info);
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+        stats.update_aux("Query cycles", (query_end - start) as f64);
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol metadata retrieval with a NULL protocol GUID, which some firmware accepts as a query for the
+/// open entries of all protocols on the handle. The UEFI specification requires `INVALID_PARAMETER` instead, so both
+/// that and `UNSUPPORTED` are accepted, and whether the query succeeded is recorded as well.
+/// EDK2-derived firmware passes the GUID to `CompareGuid` without a NULL check and faults on the call, so this is a fault
+/// probe that only runs when enabled in the configuration.
+pub(crate) fn bench_open_protocol_information_all(
+    handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // The `BootServices` wrapper only takes a GUID reference, so call the firmware directly.
+    let efi_boot_services = efi_boot_services_table(handle)?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let mut entry_buffer: *mut efi::OpenProtocolInformationEntry = core::ptr::null_mut();
+        let mut entry_count: usize = 0;
+        let start = Arch::cpu_count();
+        let status = (efi_boot_services.open_protocol_information)(
+            handle,
+            core::ptr::null_mut(),
+            &mut entry_buffer,
+            &mut entry_count,
+        );
+        let end = Arch::cpu_count();
+        match status {
+            efi::Status::SUCCESS => {
+                stats.update((end - start) as f64);
+                stats.update_aux("Null GUID query succeeded", 1.0);
+                if !entry_buffer.is_null() {
+                    BOOT_SERVICES
+                        .free_pool(entry_buffer as *mut u8)
+                        .map_err(|e| BenchError::cleanup("Failed to free open protocol information", e))?;
+                }
+            }
+            efi::Status::INVALID_PARAMETER | efi::Status::UNSUPPORTED => {
+                stats.update((end - start) as f64);
+                stats.update_aux("Null GUID query succeeded", 0.0);
+            }
+            e => return Err(BenchError::measure("Failed to get open protocol information", e)),
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol metadata retrieval for a protocol that `N` agents have open.
+/// Firmware that keeps the open entries in a linked list has to walk all of them.
+pub(crate) fn bench_open_protocol_information_n_entries<const N: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    let agent_installs = attach_consumers(protocol_install.0, N)?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let _info = BOOT_SERVICES
+            .open_protocol_information(protocol_install.0, &TEST_GUID1)
+            .map_err(|e| BenchError::measure("Failed to get open protocol information", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    // Detach the agents and uninstall the mock protocol after benchmarking.
+    detach_consumers(protocol_install.0, agent_installs)?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks handle protocol enumeration.
+pub(crate) fn bench_protocols_per_handle(
+    handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let _protocols = BOOT_SERVICES
+            .protocols_per_handle(handle)
+            .map_err(|e| BenchError::measure("Failed to get protocols per handle", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks handle protocol enumeration on a fresh handle with exactly one protocol installed.
+/// Unlike the image handle, this handle has no firmware-installed protocols, so this is the minimal-cost baseline.
+pub(crate) fn bench_protocols_per_handle_fresh(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let _protocols = BOOT_SERVICES
+            .protocols_per_handle(protocol_install.0)
+            .map_err(|e| BenchError::measure("Failed to get protocols per handle", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    // Uninstall mock protocol after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    Ok(stats)
+}
+
+/// Number of install/uninstall cycles run on the handle before `bench_protocols_per_handle_post_thrash` is timed.
+const THRASH_CYCLES: usize = 1000;
+
+/// Benchmarks handle protocol enumeration on a handle that had a second protocol installed and uninstalled
+/// `THRASH_CYCLES` times. Compared to `bench_protocols_per_handle_fresh`, this shows whether the firmware leaves stale
+/// entries behind on the handle's protocol list. The number of returned protocols is reported next to the cycles.
+pub(crate) fn bench_protocols_per_handle_post_thrash(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let base_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install base protocol", e))?;
+    for _ in 0..THRASH_CYCLES {
+        let protocol_install = BOOT_SERVICES
+            .install_protocol_interface(Some(base_install.0), Box::new(TestProtocol2 {}))
+            .map_err(|e| BenchError::setup("Failed to install protocol on existing handle", e))?;
+        BOOT_SERVICES
+            .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+            .map_err(|e| BenchError::setup("Failed to uninstall protocol", e))?;
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let protocols = BOOT_SERVICES
+            .protocols_per_handle(base_install.0)
+            .map_err(|e| BenchError::measure("Failed to get protocols per handle", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+        stats.update_aux("Protocols", protocols.len() as f64);
+    }
+
+    // Uninstall the base protocol, which also frees the handle.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(base_install.0, base_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall base protocol", e))?;
+    Ok(stats)
+}
+
+/// Benchmarks handle protocol enumeration on a fresh handle with 100 protocols installed.
+/// The returned buffer grows with the protocol count, so the allocation size is reported next to the cycles
+/// to help separate the firmware's search time from its allocator time.
+pub(crate) fn bench_protocols_per_handle_large(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let handle = install_null_protocols(MANY_GUIDS.len())?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let protocols = BOOT_SERVICES
             .protocols_per_handle(handle)
-            .map_err(|e| BenchError::BenchTest("Failed to get protocols per handle", e))?;
+            .map_err(|e| BenchError::measure("Failed to get protocols per handle", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
+        stats.update_aux("Buffer bytes", core::mem::size_of_val(&*protocols) as f64);
     }
 
+    uninstall_null_protocols(handle, MANY_GUIDS.len())?;
     Ok(stats)
 }
 
 /// Benchmarks protocol notification registration.
-pub(crate) fn bench_register_protocol_notify(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
+pub(crate) fn bench_register_protocol_notify(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     // Mock notify does nothing.
     extern "efiapi" fn mock_notify(_ptr: *mut c_void, _data: *mut i32) {}
 
-    let mut stats: Stats<f64> = Stats::new();
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let event = BOOT_SERVICES
             .create_event(EventType::NOTIFY_SIGNAL, Tpl::NOTIFY, Some(mock_notify), &mut 0 as *mut i32)
-            .map_err(|e| BenchError::BenchSetup("Failed to create valid event", e))?;
+            .map_err(|e| BenchError::setup("Failed to create valid event", e))?;
         let start = Arch::cpu_count();
         BOOT_SERVICES
             .register_protocol_notify(&efi::protocols::loaded_image::PROTOCOL_GUID, event)
-            .map_err(|e| BenchError::BenchTest("Failed to register protocol notify", e))?;
+            .map_err(|e| BenchError::measure("Failed to register protocol notify", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES.close_event(event).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol notification registration on a GUID that no firmware component uses.
+/// Comparing against `register_protocol_notify` shows whether firmware optimizes lookups of well-known GUIDs.
+pub(crate) fn bench_register_protocol_notify_unknown_guid(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    /// A GUID that is not registered by any firmware component.
+    const UNKNOWN_GUID: efi::Guid =
+        efi::Guid::from_fields(0x3c9e5a71, 0xd04b, 0x4f26, 0x8b, 0x13, &[0x6e, 0x2a, 0x95, 0xf0, 0x47, 0xcd]);
+
+    // Mock notify does nothing.
+    extern "efiapi" fn mock_notify(_ptr: *mut c_void, _data: *mut i32) {}
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let event = BOOT_SERVICES
+            .create_event(EventType::NOTIFY_SIGNAL, Tpl::NOTIFY, Some(mock_notify), &mut 0 as *mut i32)
+            .map_err(|e| BenchError::setup("Failed to create valid event", e))?;
+        let start = Arch::cpu_count();
+        BOOT_SERVICES
+            .register_protocol_notify(&UNKNOWN_GUID, event)
+            .map_err(|e| BenchError::measure("Failed to register protocol notify", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        BOOT_SERVICES.close_event(event).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol notification registration on a GUID that already has `N` registrations.
+/// Firmware that appends to a linear registration list pays an O(N) cost for each new registration.
+pub(crate) fn bench_register_protocol_notify_nth<const N: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Mock notify does nothing.
+    extern "efiapi" fn mock_notify(_ptr: *mut c_void, _data: *mut i32) {}
+
+    // Register the existing notifications.
+    let mut events = Vec::with_capacity(N);
+    for _ in 0..N {
+        let event = BOOT_SERVICES
+            .create_event(EventType::NOTIFY_SIGNAL, Tpl::NOTIFY, Some(mock_notify), &mut 0 as *mut i32)
+            .map_err(|e| BenchError::setup("Failed to create valid event", e))?;
+        BOOT_SERVICES
+            .register_protocol_notify(&TEST_GUID1, event)
+            .map_err(|e| BenchError::setup("Failed to register protocol notify", e))?;
+        events.push(event);
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let event = BOOT_SERVICES
+            .create_event(EventType::NOTIFY_SIGNAL, Tpl::NOTIFY, Some(mock_notify), &mut 0 as *mut i32)
+            .map_err(|e| BenchError::setup("Failed to create valid event", e))?;
+        let start = Arch::cpu_count();
+        BOOT_SERVICES
+            .register_protocol_notify(&TEST_GUID1, event)
+            .map_err(|e| BenchError::measure("Failed to register protocol notify", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // Closing the event also removes its registration.
+        BOOT_SERVICES.close_event(event).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+
+    // Close the existing registrations after benchmarking.
+    for event in events {
+        BOOT_SERVICES.close_event(event).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks handle lookup with the `AllHandles` search type, including allocating the returned buffer.
+/// The number of returned handles is recorded as a secondary statistic.
+pub(crate) fn bench_locate_all_handles(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let handles = BOOT_SERVICES
+            .locate_handle(HandleSearchType::AllHandle)
+            .map_err(|e| BenchError::measure("Failed to locate all handles", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+        stats.update_aux("Handles returned", handles.len() as f64);
+    }
+    Ok(stats)
+}
+
+/// Benchmarks handle lookup by protocol with `N` handles supporting the protocol.
+/// The result grows with `N`, which characterizes the search complexity of the firmware's handle database.
+pub(crate) fn bench_locate_handle_by_protocol_n<const N: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut protocol_installs = Vec::with_capacity(N);
+    for _ in 0..N {
+        let protocol_install = BOOT_SERVICES
+            .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+            .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+        protocol_installs.push(protocol_install);
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let _handles = BOOT_SERVICES
+            .locate_handle(HandleSearchType::ByProtocol(&TEST_GUID1))
+            .map_err(|e| BenchError::measure("Failed to locate handle by protocol", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    // Uninstall mock protocols after benchmarking.
+    for protocol_install in protocol_installs {
+        BOOT_SERVICES
+            .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+            .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks handle lookup by a protocol that is not installed on any handle.
+/// The firmware must scan the whole handle database before failing, which is the worst case for a driver's `Supported`.
+pub(crate) fn bench_locate_handle_not_found(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        // `TEST_GUID2` is only installed temporarily by other benchmarks, so no handle supports it here.
+        let result = BOOT_SERVICES.locate_handle(HandleSearchType::ByProtocol(&TEST_GUID2));
+        let end = Arch::cpu_count();
+        match result {
+            Err(efi::Status::NOT_FOUND) => stats.update((end - start) as f64),
+            Err(e) => return Err(BenchError::measure("Failed to locate handle by protocol", e)),
+            Ok(_) => {
+                return Err(BenchError::measure("Locate handle unexpectedly found a handle", efi::Status::SUCCESS));
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// Benchmarks handle lookup through a protocol notify registration key.
+/// This is the `ByRegisterNotify` path of `locate_handle`, which is separate from the `ByProtocol` search.
+pub(crate) fn bench_locate_handle_by_register_notify(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Mock notify does nothing.
+    extern "efiapi" fn mock_notify(_ptr: *mut c_void, _data: *mut i32) {}
+
+    // Register for `TestProtocol1` installations to get a search key.
+    let event = BOOT_SERVICES
+        .create_event(EventType::NOTIFY_SIGNAL, Tpl::NOTIFY, Some(mock_notify), &mut 0 as *mut i32)
+        .map_err(|e| BenchError::setup("Failed to create valid event", e))?;
+    let registration = BOOT_SERVICES
+        .register_protocol_notify(&TEST_GUID1, event)
+        .map_err(|e| BenchError::setup("Failed to register protocol notify", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        // Each install queues exactly one new handle for the registration key.
+        let protocol_install = BOOT_SERVICES
+            .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+            .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+
+        let start = Arch::cpu_count();
+        let _handles = BOOT_SERVICES
+            .locate_handle(HandleSearchType::ByRegisterNotify(registration))
+            .map_err(|e| BenchError::measure("Failed to locate handle by register notify", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
 
-        BOOT_SERVICES.close_event(event).map_err(|e| BenchError::BenchCleanup("Failed to close event", e))?;
+        BOOT_SERVICES
+            .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+            .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
     }
 
+    // Closing the event also removes the notify registration.
+    BOOT_SERVICES.close_event(event).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
+
     Ok(stats)
 }
 
 /// Benchmarks protocol update performance.
+/// Outside the timed region, each iteration also checks that `handle_protocol` returns the new interface pointer
+/// rather than the replaced one, so the benchmark doubles as a partial correctness check.
 pub(crate) fn bench_reinstall_protocol_interface(
     _handle: efi::Handle,
     num_calls: usize,
-) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
-        let prev_interface = Box::new(TestProtocol1 {});
-        let new_interface = Box::new(TestProtocol1 {});
+        // The interface has a non-zero size so that the old and new interface pointers can be told apart.
+        let prev_interface = Box::new(TestDataProtocol { _value: 0 });
+        let new_interface = Box::new(TestDataProtocol { _value: 0 });
         let protocol_install = BOOT_SERVICES
             .install_protocol_interface(None, prev_interface)
-            .map_err(|e| BenchError::BenchSetup("Failed to install dummy protocol", e))?;
+            .map_err(|e| BenchError::setup("Failed to install dummy protocol", e))?;
+        let old_ptr = protocol_install.1.ptr_value;
 
         let start = Arch::cpu_count();
         let reinstall = BOOT_SERVICES
             .reinstall_protocol_interface(protocol_install.0, protocol_install.1, new_interface)
-            .map_err(|e| BenchError::BenchTest("Failed to reinstall protocol interface", e))?;
+            .map_err(|e| BenchError::measure("Failed to reinstall protocol interface", e))?;
 
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
 
+        // SAFETY: The resulting interface reference is only used for its address.
+        let current_ptr = (unsafe {
+            BOOT_SERVICES
+                .handle_protocol::<TestDataProtocol>(protocol_install.0)
+                .map_err(|e| BenchError::measure("Failed to find reinstalled protocol", e))
+        })? as *mut TestDataProtocol as usize;
+        if current_ptr == old_ptr {
+            return Err(BenchError::measure("Old interface still installed after reinstall", efi::Status::SUCCESS));
+        }
+        if current_ptr != reinstall.0.ptr_value {
+            return Err(BenchError::measure(
+                "Reinstalled interface not returned by handle_protocol",
+                efi::Status::SUCCESS,
+            ));
+        }
+
         // Cleanup: Uninstall the protocol after benchmarking. (It will be installed and reinstalled in the next iteration.)
         BOOT_SERVICES
             .uninstall_protocol_interface(protocol_install.0, reinstall.0)
-            .map_err(|e| BenchError::BenchCleanup("Failed to uninstall protocol interface", e))?;
+            .map_err(|e| BenchError::cleanup("Failed to uninstall protocol interface", e))?;
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol update performance while the protocol has `CONSUMERS` consumers attached.
+/// This is the production scenario where a driver reinstalls a protocol to propagate interface updates:
+/// the firmware must disconnect the managing driver, swap the interface, and reconnect the driver.
+///
+/// UEFI only allows a single `BY_DRIVER` open per protocol, so the first consumer is a mock driver that holds the
+/// `BY_DRIVER` open and the remaining consumers are agents holding a `GET_PROTOCOL` open.
+pub(crate) fn bench_reinstall_with_consumers<const CONSUMERS: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+
+    // Install the mock driver and let it open the protocol `BY_DRIVER`.
+    let driver_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install driver protocol", e))?;
+    let driver_binding = BOOT_SERVICES
+        .install_protocol_interface(
+            Some(driver_install.0),
+            Box::new(efi::protocols::driver_binding::Protocol {
+                version: 10,
+                supported: consumer_supported,
+                start: consumer_start,
+                stop: consumer_stop,
+                driver_binding_handle: driver_install.0,
+                image_handle: driver_install.0,
+            }),
+        )
+        .map_err(|e| BenchError::setup("Failed to install driver binding", e))?;
+    // SAFETY: All handles are valid (constructed by benchmark).
+    unsafe {
+        BOOT_SERVICES
+            .connect_controller(protocol_install.0, vec![driver_install.0], core::ptr::null_mut(), false)
+            .map_err(|e| BenchError::setup("Failed to connect consumer driver", e))?;
+    }
+
+    // Attach the remaining consumers.
+    let agent_installs = attach_consumers(protocol_install.0, CONSUMERS.saturating_sub(1))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let reinstall = BOOT_SERVICES
+            .reinstall_protocol_interface(protocol_install.0, protocol_install.1, Box::new(TestProtocol1 {}))
+            .map_err(|e| BenchError::measure("Failed to reinstall protocol interface", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // The new interface is reinstalled in the next iteration.
+        protocol_install.1 = reinstall.0;
+        // The reinstall dropped the agents' `GET_PROTOCOL` opens, so every iteration starts with all consumers.
+        reopen_consumers(protocol_install.0, &agent_installs)?;
+    }
+
+    // Detach all consumers and uninstall mock protocols after benchmarking.
+    BOOT_SERVICES
+        .disconnect_controller(protocol_install.0, None, None)
+        .map_err(|e| BenchError::cleanup("Failed to disconnect consumer driver", e))?;
+    detach_consumers(protocol_install.0, agent_installs)?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(driver_binding.0, driver_binding.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall driver binding", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(driver_install.0, driver_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall driver protocol", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol update performance while `M` protocol notify listeners are registered on the protocol.
+/// Every reinstall signals all listeners, so this characterizes the fan-out cost of the reinstall notify path.
+pub(crate) fn bench_reinstall_with_m_listeners<const M: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Mock notify does nothing.
+    extern "efiapi" fn mock_notify(_ptr: *mut c_void, _data: *mut i32) {}
+
+    let mut protocol_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+        .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+
+    // Register the listeners.
+    let mut events = Vec::with_capacity(M);
+    for _ in 0..M {
+        let event = BOOT_SERVICES
+            .create_event(EventType::NOTIFY_SIGNAL, Tpl::NOTIFY, Some(mock_notify), &mut 0 as *mut i32)
+            .map_err(|e| BenchError::setup("Failed to create valid event", e))?;
+        BOOT_SERVICES
+            .register_protocol_notify(&TEST_GUID1, event)
+            .map_err(|e| BenchError::setup("Failed to register protocol notify", e))?;
+        events.push(event);
+    }
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        let reinstall = BOOT_SERVICES
+            .reinstall_protocol_interface(protocol_install.0, protocol_install.1, Box::new(TestProtocol1 {}))
+            .map_err(|e| BenchError::measure("Failed to reinstall protocol interface", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // The new interface is reinstalled in the next iteration.
+        protocol_install.1 = reinstall.0;
+    }
+
+    // Close the listeners and uninstall the mock protocol after benchmarking.
+    for event in events {
+        BOOT_SERVICES.close_event(event).map_err(|e| BenchError::cleanup("Failed to close event", e))?;
     }
+    BOOT_SERVICES
+        .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol", e))?;
 
     Ok(stats)
 }
@@ -284,29 +1987,126 @@ pub(crate) fn bench_reinstall_protocol_interface(
 pub(crate) fn bench_uninstall_protocol_interface(
     _handle: efi::Handle,
     num_calls: usize,
-) -> Result<Stats<f64>, BenchError> {
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
     let mut protocol_install = BOOT_SERVICES
         .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-        .map_err(|e| BenchError::BenchSetup("Failed to install dummy protocol", e))?;
-    let mut stats: Stats<f64> = Stats::new();
+        .map_err(|e| BenchError::setup("Failed to install dummy protocol", e))?;
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         BOOT_SERVICES
             .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
-            .map_err(|e| BenchError::BenchTest("Failed to uninstall protocol interface", e))?;
+            .map_err(|e| BenchError::measure("Failed to uninstall protocol interface", e))?;
         let end = Arch::cpu_count();
         stats.update((end - start) as f64);
 
         // Reinstall for next iteration.
         protocol_install = BOOT_SERVICES
             .install_protocol_interface(None, Box::new(TestProtocol1 {}))
-            .map_err(|e| BenchError::BenchCleanup("Failed to install a new dummy protocol", e))?;
+            .map_err(|e| BenchError::cleanup("Failed to install a new dummy protocol", e))?;
     }
 
     // Installation from last iteration cleanup.
     BOOT_SERVICES
         .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
-        .map_err(|e| BenchError::BenchCleanup("Failed to uninstall protocol interface", e))?;
+        .map_err(|e| BenchError::cleanup("Failed to uninstall protocol interface", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks protocol uninstallation while a mock driver has the protocol open `BY_DRIVER`.
+/// The firmware must disconnect the driver, whose `Stop` closes the protocol, before it can remove the protocol.
+pub(crate) fn bench_uninstall_protocol_with_consumers(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    // Install the mock driver that opens the protocol `BY_DRIVER` when connected.
+    let driver_install = BOOT_SERVICES
+        .install_protocol_interface(None, Box::new(TestProtocol2 {}))
+        .map_err(|e| BenchError::setup("Failed to install driver protocol", e))?;
+    let driver_binding = BOOT_SERVICES
+        .install_protocol_interface(
+            Some(driver_install.0),
+            Box::new(efi::protocols::driver_binding::Protocol {
+                version: 10,
+                supported: consumer_supported,
+                start: consumer_start,
+                stop: consumer_stop,
+                driver_binding_handle: driver_install.0,
+                image_handle: driver_install.0,
+            }),
+        )
+        .map_err(|e| BenchError::setup("Failed to install driver binding", e))?;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let protocol_install = BOOT_SERVICES
+            .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+            .map_err(|e| BenchError::setup("Failed to install protocol", e))?;
+        // SAFETY: All handles are valid (constructed by benchmark).
+        unsafe {
+            BOOT_SERVICES
+                .connect_controller(protocol_install.0, vec![driver_install.0], core::ptr::null_mut(), false)
+                .map_err(|e| BenchError::setup("Failed to connect consumer driver", e))?;
+        }
+
+        let start = Arch::cpu_count();
+        BOOT_SERVICES
+            .uninstall_protocol_interface(protocol_install.0, protocol_install.1)
+            .map_err(|e| BenchError::measure("Failed to uninstall protocol interface with consumers", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    // Uninstall the mock driver after benchmarking.
+    BOOT_SERVICES
+        .uninstall_protocol_interface(driver_binding.0, driver_binding.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall driver binding", e))?;
+    BOOT_SERVICES
+        .uninstall_protocol_interface(driver_install.0, driver_install.1)
+        .map_err(|e| BenchError::cleanup("Failed to uninstall driver protocol", e))?;
+
+    Ok(stats)
+}
+
+/// Benchmarks removal of the first of two protocols on a handle, so the handle survives the uninstall.
+/// Compared against `bench_uninstall_protocol_interface`, where the handle is destroyed along with its last protocol,
+/// this separates the cost of freeing the handle from the cost of unlinking a protocol.
+pub(crate) fn bench_uninstall_protocol_interface_non_last(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        let first_install = BOOT_SERVICES
+            .install_protocol_interface(None, Box::new(TestProtocol1 {}))
+            .map_err(|e| BenchError::setup("Failed to install first protocol", e))?;
+        let second_install = BOOT_SERVICES
+            .install_protocol_interface(Some(first_install.0), Box::new(TestProtocol2 {}))
+            .map_err(|e| BenchError::setup("Failed to install second protocol", e))?;
+
+        let start = Arch::cpu_count();
+        BOOT_SERVICES
+            .uninstall_protocol_interface(first_install.0, first_install.1)
+            .map_err(|e| BenchError::measure("Failed to uninstall protocol interface", e))?;
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+
+        // The handle must survive with the second protocol still installed.
+        // SAFETY: The resulting interface reference is not used at all.
+        (unsafe {
+            BOOT_SERVICES
+                .handle_protocol::<TestProtocol2>(second_install.0)
+                .map_err(|e| BenchError::measure("Second protocol missing after uninstalling the first", e))
+        })?;
+
+        BOOT_SERVICES
+            .uninstall_protocol_interface(second_install.0, second_install.1)
+            .map_err(|e| BenchError::cleanup("Failed to uninstall second protocol", e))?;
+    }
 
     Ok(stats)
 }
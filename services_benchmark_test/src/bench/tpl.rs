@@ -8,15 +8,22 @@
 use mu_rust_helpers::perf_timer::{Arch, ArchFunctionality as _};
 use patina::boot_services::{BootServices as _, tpl::Tpl};
 use r_efi::efi::{self};
-use rolling_stats::Stats;
 
-use crate::{BOOT_SERVICES, error::BenchError};
-
-const TPL_HIGH_LEVEL: Tpl = Tpl(31);
+use crate::{
+    BOOT_SERVICES,
+    bench::{TPL_HIGH_LEVEL, record_window_throughput},
+    config::BenchConfig,
+    error::BenchError,
+    measure::Measurements,
+};
 
 /// Benchmarks interrupt disable performance.
-pub(crate) fn bench_raise_tpl(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+pub(crate) fn bench_raise_tpl(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
     for _ in 0..num_calls {
         let start = Arch::cpu_count();
         // Use TPL_HIGH_LEVEL to test impact of interrupts.
@@ -31,8 +38,12 @@ pub(crate) fn bench_raise_tpl(_handle: efi::Handle, num_calls: usize) -> Result<
 }
 
 /// Benchmarks interrupt enable performance.
-pub(crate) fn bench_restore_tpl(_handle: efi::Handle, num_calls: usize) -> Result<Stats<f64>, BenchError> {
-    let mut stats: Stats<f64> = Stats::new();
+pub(crate) fn bench_restore_tpl(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let mut stats = Measurements::new(num_calls, config);
     let tpl_options = [Tpl::APPLICATION, Tpl::CALLBACK, Tpl::NOTIFY, TPL_HIGH_LEVEL];
     for i in 0..num_calls {
         // Rotate between different TPL levels to test all scenarios.
@@ -46,3 +57,51 @@ pub(crate) fn bench_restore_tpl(_handle: efi::Handle, num_calls: usize) -> Resul
 
     Ok(stats)
 }
+
+/// Benchmarks the maximum TPL toggle rate as the number of raise/restore pairs completed in a 1 ms window.
+/// This is what interrupt-heavy drivers weigh when deciding whether to use fine-grained TPL protection.
+pub(crate) fn bench_tpl_toggle_frequency(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    const WINDOW_MS: u64 = 1;
+
+    let mut stats = Measurements::new(num_calls, config);
+    for _ in 0..num_calls {
+        record_window_throughput(&mut stats, "Toggle operations per ms", WINDOW_MS, || {
+            let old_tpl = BOOT_SERVICES.raise_tpl(TPL_HIGH_LEVEL);
+            BOOT_SERVICES.restore_tpl(old_tpl);
+            Ok(())
+        })?;
+    }
+
+    Ok(stats)
+}
+
+/// Benchmarks a round trip of `N` nested TPL raises followed by `N` restores.
+/// The raises always end at `TPL_HIGH_LEVEL`, so the depths differ only in the nesting overhead.
+pub(crate) fn bench_tpl_nested_depth_n<const N: usize>(
+    _handle: efi::Handle,
+    num_calls: usize,
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    let tpl_levels = [Tpl::APPLICATION, Tpl::CALLBACK, Tpl::NOTIFY, TPL_HIGH_LEVEL];
+    let nested_levels = &tpl_levels[tpl_levels.len() - N..];
+
+    let mut stats = Measurements::new(num_calls, config);
+    let mut old_tpls = [Tpl::APPLICATION; N];
+    for _ in 0..num_calls {
+        let start = Arch::cpu_count();
+        for (old_tpl, &tpl) in old_tpls.iter_mut().zip(nested_levels) {
+            *old_tpl = BOOT_SERVICES.raise_tpl(tpl);
+        }
+        for &old_tpl in old_tpls.iter().rev() {
+            BOOT_SERVICES.restore_tpl(old_tpl);
+        }
+        let end = Arch::cpu_count();
+        stats.update((end - start) as f64);
+    }
+
+    Ok(stats)
+}
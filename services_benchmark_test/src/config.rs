@@ -0,0 +1,63 @@
+//! Configuration for a benchmark run.
+//!
+//! Copyright (c) Microsoft Corporation.
+//!
+//! SPDX-License-Identifier: Apache-2.0
+//!
+
+/// Options that control how the benchmarks are run.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+    /// Number of warmup iterations run before each benchmark, as a percentage of its measured iterations.
+    pub warmup_percent: usize,
+    /// Record the cycle count of every measured iteration and write a histogram of the distribution for each benchmark
+    /// after the results table. This requires memory proportional to the number of iterations. Warmup iterations are
+    /// never recorded.
+    pub verbose_samples: bool,
+    /// How the number of measured iterations of each benchmark is chosen.
+    pub mode: BenchMode,
+    /// UEFI revision of the firmware, as found in the system table header's `revision` field.
+    /// Benchmarks that need a newer revision are skipped. `None` runs all benchmarks.
+    pub uefi_revision: Option<u32>,
+    /// List the benchmarks that would run, with their category and iteration count, without running them.
+    pub dry_run: bool,
+    /// Leave iterations flagged as outliers out of the statistics. Outliers are counted in the Outliers column either
+    /// way.
+    pub exclude_outliers: bool,
+    /// Number of standard deviations an iteration must be away from the running mean to be flagged as an outlier.
+    pub outlier_threshold_factor: f64,
+    /// Run the benchmarks that pass invalid input to a service to time its error path. Firmware without the expected
+    /// validation may crash or trip a debug assertion on that input, so these benchmarks are skipped by default.
+    pub run_fault_probes: bool,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup_percent: 10,
+            verbose_samples: false,
+            mode: BenchMode::Fixed,
+            uefi_revision: None,
+            dry_run: false,
+            exclude_outliers: false,
+            outlier_threshold_factor: 3.0,
+            run_fault_probes: false,
+        }
+    }
+}
+
+/// How the number of measured iterations of each benchmark is chosen.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum BenchMode {
+    /// Run the fixed iteration count registered for each benchmark.
+    #[default]
+    Fixed,
+    /// Run iterations in batches of a tenth of the registered iteration count until the coefficient of variation
+    /// (standard deviation / mean) of the cycle counts drops below `cv_threshold`, or `max_iterations` is reached.
+    Adaptive {
+        /// Upper bound on the number of measured iterations.
+        max_iterations: usize,
+        /// Coefficient of variation below which the result is considered stable, e.g. `0.05` for 5%.
+        cv_threshold: f64,
+    },
+}
@@ -9,25 +9,80 @@ use core::fmt;
 
 use r_efi::efi;
 
+/// The phase of a benchmark run in which an error occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchPhase {
+    /// Preparing the resources needed by the benchmark.
+    Setup,
+    /// Running the warmup iterations before the measurement.
+    Warmup,
+    /// Running the measured service calls.
+    Measure,
+    /// Releasing the resources used by the benchmark.
+    Cleanup,
+    /// Writing the benchmark results.
+    Output,
+}
+
 #[derive(Debug)]
 pub enum BenchError {
-    BenchSetup(&'static str, efi::Status),
-    BenchTest(&'static str, efi::Status),
-    BenchCleanup(&'static str, efi::Status),
+    Phase {
+        phase: BenchPhase,
+        msg: &'static str,
+        status: efi::Status,
+    },
     WriteOutput(&'static str, core::fmt::Error),
+    /// The system lacks something the benchmark needs, so it is reported as skipped instead of failed.
+    Unsupported(&'static str),
+}
+
+impl BenchError {
+    pub(crate) const fn setup(msg: &'static str, status: efi::Status) -> Self {
+        BenchError::Phase { phase: BenchPhase::Setup, msg, status }
+    }
+
+    pub(crate) const fn measure(msg: &'static str, status: efi::Status) -> Self {
+        BenchError::Phase { phase: BenchPhase::Measure, msg, status }
+    }
+
+    pub(crate) const fn cleanup(msg: &'static str, status: efi::Status) -> Self {
+        BenchError::Phase { phase: BenchPhase::Cleanup, msg, status }
+    }
+
+    pub(crate) const fn unsupported(reason: &'static str) -> Self {
+        BenchError::Unsupported(reason)
+    }
+
+    /// Re-labels a failed service call as having happened during the warmup iterations.
+    pub(crate) fn into_warmup(self) -> Self {
+        match self {
+            BenchError::Phase { phase: BenchPhase::Measure, msg, status } => {
+                BenchError::Phase { phase: BenchPhase::Warmup, msg, status }
+            }
+            e => e,
+        }
+    }
+
+    /// Returns the phase in which the error occurred.
+    pub fn phase(&self) -> Option<BenchPhase> {
+        match self {
+            BenchError::Phase { phase, .. } => Some(*phase),
+            BenchError::WriteOutput(..) => Some(BenchPhase::Output),
+            BenchError::Unsupported(..) => None,
+        }
+    }
 }
 
 impl fmt::Display for BenchError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            BenchError::BenchSetup(msg, status)
-            | BenchError::BenchTest(msg, status)
-            | BenchError::BenchCleanup(msg, status) => {
-                write!(f, "{} with error {:?}", msg, status)
+            BenchError::Phase { phase, msg, status } => {
+                write!(f, "{:?}: {} with error {:?}", phase, msg, status)
             }
             BenchError::WriteOutput(msg, err) => {
                 write!(f, "{} with formatting error {:?}", msg, err)
             }
+            BenchError::Unsupported(reason) => write!(f, "Unsupported: {}", reason),
         }
     }
 }
@@ -10,10 +10,24 @@
 //! image handle and system table. The library will execute a set of predefined benchmarks and print
 //! the results to the UEFI console.
 //!
+//! Use `bench_start_with_config` to customize the run with a `BenchConfig` and to redirect the results
+//! to a different `OutputSink`. Setting `BenchConfig::mode` to `BenchMode::Adaptive` runs each benchmark until
+//! its results stabilize instead of for a fixed number of iterations. Setting `BenchConfig::dry_run` lists the
+//! benchmarks that would run, with their category and iteration count, without timing them.
+//! Benchmarks that pass invalid input to a service, which may crash firmware lacking the expected validation, are
+//! skipped unless `BenchConfig::run_fault_probes` is set.
+//!
 //! ## Output
 //!
+//! A metadata table with the category, minimum UEFI version, and description of every benchmark precedes the results.
 //! The benchmark results include the name of each tested service, total cycles consumed, number of calls,
-//! and average cycles per operation.
+//! and average cycles per operation. When `BenchConfig::verbose_samples` is set, a histogram of the per-iteration
+//! cycle counts of each benchmark follows the results table. The Outliers column counts the iterations that were more
+//! than `BenchConfig::outlier_threshold_factor` standard deviations away from the running mean, which are left out of
+//! the statistics when `BenchConfig::exclude_outliers` is set.
+//!
+//! The formatting is done by `PerfReport`, which can also write a list of `BenchResult`s as CSV or JSON, including the
+//! recorded per-iteration cycle counts.
 //!
 //! ## License
 //!
@@ -33,137 +47,191 @@ extern crate alloc;
 use std as alloc;
 
 use alloc::{
+    format,
     string::{String, ToString},
     vec::Vec,
 };
 
-use core::fmt::Write;
 use mu_rust_helpers::perf_timer::{Arch, ArchFunctionality as _};
-use rolling_stats::Stats;
 
 use patina::boot_services::StandardBootServices;
 use r_efi::efi;
 
-use crate::{error::BenchError, measure::BENCH_FNS};
+use crate::measure::{BENCH_FNS, BenchFn, Measurements};
+
+pub use crate::{
+    config::{BenchConfig, BenchMode},
+    error::{BenchError, BenchPhase},
+    output::{ConsoleOutputSink, NullOutputSink, OutputSink},
+    report::{
+        BenchResult, BenchStatus, PerfReport, write_dry_run_row, write_headers, write_histogram, write_metadata_table,
+        write_result_row,
+    },
+};
 
+/// Runs all benchmarks with the default configuration and prints the results to the UEFI console.
 pub fn bench_start(handle: efi::Handle) -> Result<(), BenchError> {
-    log::info!("Starting Services Benchmark Test...");
+    bench_start_with_config(handle, &BenchConfig::default(), &ConsoleOutputSink)
+}
 
-    let mut output_buf = String::new();
+/// Runs all benchmarks with the given configuration and writes the results table to `sink`.
+pub fn bench_start_with_config(
+    handle: efi::Handle,
+    config: &BenchConfig,
+    sink: &dyn OutputSink,
+) -> Result<(), BenchError> {
+    log::info!("Starting Services Benchmark Test...");
 
-    write_headers(&mut output_buf)?;
+    let mut results = Vec::new();
 
     for (bf, num_calls) in BENCH_FNS {
-        // Run a few warmup iterations. (10% of the benchmark iterations).
-        (bf.func)(handle, num_calls / 10)?;
+        if let Some(uefi_revision) = config.uefi_revision
+            && !bf.is_supported_by(uefi_revision)
+        {
+            let (major, minor) = bf.min_uefi_version;
+            log::warn!("Skipping benchmark {}: requires UEFI {}.{}", bf.name, major, minor);
+            results.push(BenchResult {
+                notes: format!("Requires UEFI {}.{}", major, minor),
+                ..BenchResult::unmeasured(bf.name, bf.category, BenchStatus::Skipped)
+            });
+            continue;
+        }
+
+        if bf.fault_probe && !config.run_fault_probes {
+            log::info!("Skipping benchmark {}: fault probes are disabled", bf.name);
+            results.push(BenchResult {
+                notes: "Fault probe, see BenchConfig::run_fault_probes".to_string(),
+                ..BenchResult::unmeasured(bf.name, bf.category, BenchStatus::Skipped)
+            });
+            continue;
+        }
 
-        let (bench_name, bench_func) = (bf.name, bf.func);
-        let cycles_res = bench_func(handle, num_calls);
+        if config.dry_run {
+            results
+                .push(BenchResult { num_calls, ..BenchResult::unmeasured(bf.name, bf.category, BenchStatus::DryRun) });
+            continue;
+        }
+
+        // Run a few warmup iterations. Their measurements are discarded, so they do not record samples.
+        let warmup_config = BenchConfig { verbose_samples: false, ..*config };
+        match (bf.func)(handle, num_calls * config.warmup_percent / 100, &warmup_config) {
+            Ok(_) => (),
+            Err(BenchError::Unsupported(reason)) => {
+                log::warn!("Skipping benchmark {}: {}", bf.name, reason);
+                results.push(BenchResult {
+                    notes: reason.to_string(),
+                    ..BenchResult::unmeasured(bf.name, bf.category, BenchStatus::Skipped)
+                });
+                continue;
+            }
+            Err(e) => return Err(e.into_warmup()),
+        }
+
+        let bench_name = bf.name;
+        let cycles_res = run_bench(bf.func, handle, num_calls, config);
         match cycles_res {
-            Ok(cycles_stats) => {
+            Ok(measurements) => {
+                let cycles_stats = measurements.stats;
                 // Calculate total time in milliseconds. Formula: ms = cycles / (cycles / s) * 1000.
                 let total_time_ms = (cycles_stats.count as f64) / (Arch::perf_frequency() as f64) * 1000.0;
-                write_result_row(&mut output_buf, bench_name, cycles_stats, total_time_ms, num_calls)?;
+                // The adaptive mode may measure a different number of iterations than registered.
+                let measured_calls = cycles_stats.count;
+                let notes = match &measurements.aux {
+                    Some(aux) => {
+                        format!("{}: {:.1} (min {}, max {})", aux.label, aux.stats.mean, aux.stats.min, aux.stats.max)
+                    }
+                    None => String::new(),
+                };
+                results.push(BenchResult {
+                    name: bench_name.to_string(),
+                    category: bf.category,
+                    status: BenchStatus::Measured,
+                    stats: cycles_stats,
+                    total_time_ms,
+                    num_calls: measured_calls,
+                    outliers: measurements.outliers,
+                    notes,
+                    samples: measurements.samples,
+                });
+                // The throughput goes in the cycle columns of its own row, like the ratio rows.
+                if let Some(throughput) = measurements.throughput {
+                    results.push(BenchResult {
+                        name: format!("{} throughput", bench_name),
+                        num_calls: throughput.stats.count,
+                        stats: throughput.stats,
+                        notes: throughput.label.to_string(),
+                        ..BenchResult::unmeasured(bench_name, bf.category, BenchStatus::Measured)
+                    });
+                }
+                // Each point of a data series gets its own row after the benchmark's row.
+                if let Some(series) = measurements.series {
+                    for (value, point_stats) in series.points {
+                        // Formula: ms = total cycles / (cycles / s) * 1000, with total cycles = mean * count.
+                        let point_cycles = point_stats.mean * point_stats.count as f64;
+                        results.push(BenchResult {
+                            name: format!("{} ({} {})", bench_name, value, series.unit),
+                            total_time_ms: point_cycles / (Arch::perf_frequency() as f64) * 1000.0,
+                            num_calls: point_stats.count,
+                            stats: point_stats,
+                            ..BenchResult::unmeasured(bench_name, bf.category, BenchStatus::Measured)
+                        });
+                    }
+                }
+            }
+            Err(BenchError::Unsupported(reason)) => {
+                log::warn!("Skipping benchmark {}: {}", bench_name, reason);
+                results.push(BenchResult {
+                    notes: reason.to_string(),
+                    ..BenchResult::unmeasured(bench_name, bf.category, BenchStatus::Skipped)
+                });
             }
             Err(e) => {
                 log::error!("Benchmark {} failed: {:?}", bench_name, e);
                 debug_assert!(false);
                 // In case of failure write 0s and note failure.
-                write_result_row(
-                    &mut output_buf,
-                    (bench_name.to_string() + " (Failed)").as_str(),
-                    Stats::default(),
-                    0.0,
-                    0,
-                )?;
+                results.push(BenchResult::unmeasured(bench_name, bf.category, BenchStatus::Failed));
             }
         }
     }
 
+    let output_buf = PerfReport::new(results).as_markdown();
     log::info!("{}", output_buf);
-    // SAFETY: `st` is a valid pointer to SystemTable provided by UEFI firmware in `efi_main`.
-    unsafe { print_to_console(output_buf.as_str()) };
+    sink.write(output_buf.as_str());
 
     Ok(())
 }
 
-// Writes the header rows for the fixed-width results markdown table.
-pub fn write_headers(output_buf: &mut String) -> Result<(), BenchError> {
-    // Column headers.
-    writeln!(
-        output_buf,
-        "| {:<32} | {:>14} | {:>12} | {:>15} | {:>15} | {:>12} | {:>12} | {:>12} |",
-        "Name",
-        "Total cycles",
-        "Total calls",
-        "Cycles/op",
-        "Total time (ms)",
-        "Min cycles",
-        "Max cycles",
-        "SD [cycles]"
-    )
-    .map_err(|e| BenchError::WriteOutput("Write table header failed", e))?;
-    // Column separators.
-    writeln!(
-        output_buf,
-        "| {:-<32} | {:-<14} | {:-<12} | {:-<15} | {:-<15} | {:-<12} | {:-<12} | {:-<12} |",
-        "-", "-", "-", "-", "-", "-", "-", "-"
-    )
-    .map_err(|e| BenchError::WriteOutput("Write table header failed", e))?;
-    Ok(())
-}
-
-pub fn write_result_row(
-    output_buf: &mut String,
-    bench_name: &str,
-    stats: Stats<f64>,
-    total_time_ms: f64,
+/// Runs the measured iterations of a benchmark according to `config.mode`.
+fn run_bench(
+    bench_func: BenchFn,
+    handle: efi::Handle,
     num_calls: usize,
-) -> Result<(), BenchError> {
-    writeln!(
-        output_buf,
-        "| {:<32} | {:>14} | {:>12} | {:>15} | {:>15.3} | {:>12} | {:>12} | {:>12.2} |",
-        bench_name,
-        stats.count, // Format as usize for better readability. Partial cycles don't really matter.
-        num_calls,
-        stats.mean,
-        total_time_ms,
-        stats.min,
-        stats.max,
-        stats.std_dev as usize, // Format as usize for better readability. Partial cycles don't really matter.
-    )
-    .map_err(|e| BenchError::WriteOutput("Write table header failed", e))?;
-    Ok(())
-}
-
-/// Print a message to the UEFI console output.
-///
-/// # Safety
-/// The caller must ensure that the UEFI System Table pointer has been initialized.
-pub unsafe fn print_to_console(message: &str) {
-    let st = uefi::table::system_table_raw();
-    if let Some(st_ptr) = st {
-        let st = st_ptr.as_ptr();
-        // SAFETY: The `uefi` crate guarantees that the System Table pointer is valid after initialization.
-        let system_table = unsafe { &*st };
-        let con_out = system_table.stdout;
-
-        if con_out.is_null() {
-            return;
+    config: &BenchConfig,
+) -> Result<Measurements, BenchError> {
+    match config.mode {
+        BenchMode::Fixed => bench_func(handle, num_calls, config),
+        BenchMode::Adaptive { max_iterations, cv_threshold } => {
+            let batch_size = (num_calls / 10).max(1);
+            let mut measurements = bench_func(handle, batch_size.min(max_iterations), config)?;
+            // At least two iterations are needed for a meaningful standard deviation.
+            while measurements.stats.count < max_iterations
+                && (measurements.stats.count < 2 || measurements.coefficient_of_variation() >= cv_threshold)
+            {
+                let batch = bench_func(handle, batch_size.min(max_iterations - measurements.stats.count), config)?;
+                // A benchmark that records no iterations was skipped and will not make progress.
+                if batch.stats.count == 0 {
+                    break;
+                }
+                measurements.merge(batch);
+            }
+            Ok(measurements)
         }
-
-        // Convert the message to UTF-16 for UEFI console output.
-        let mut utf16_buffer: Vec<u16> = message.encode_utf16().collect();
-        utf16_buffer.push(0); // Null terminator.
-
-        // Call the UEFI console output function.
-        // SAFETY: If the safety conditions are met, the UEFI console output function will be valid.
-        let output_string = unsafe { (*con_out).output_string };
-        // SAFETY: If the safety conditions are met, the UEFI console output function will be valid.
-        let _ = unsafe { output_string(con_out, utf16_buffer.as_ptr() as *mut u16) };
     }
 }
 
 mod bench;
+mod config;
 mod error;
 mod measure;
+mod output;
+mod report;
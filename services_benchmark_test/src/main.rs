@@ -13,7 +13,7 @@ cfg_if::cfg_if! {
     if #[cfg(all(target_os = "uefi"))] {
         use core::panic::PanicInfo;
         use uefi::prelude::*;
-        use services_benchmark_test::bench_start;
+        use services_benchmark_test::{bench_start_with_config, BenchConfig, ConsoleOutputSink};
         use r_efi::efi;
         use services_benchmark_test::BOOT_SERVICES;
         use log::LevelFilter;
@@ -25,6 +25,7 @@ cfg_if::cfg_if! {
             uefi::helpers::init().unwrap();
             log::info!("UEFI Services Benchmark Test Entry Point");
 
+            let mut config = BenchConfig::default();
             let st = uefi::table::system_table_raw();
             if let Some(st_ptr) = st {
                 let st = st_ptr.as_ptr();
@@ -33,12 +34,13 @@ cfg_if::cfg_if! {
                 // SAFETY: `uefi` crate ensures that the boot services pointer is valid after initialization.
                 let bs = unsafe { &*(system_table.boot_services as *const efi::BootServices) };
                 BOOT_SERVICES.init(bs);
+                config.uefi_revision = Some(system_table.header.revision.0);
             }
 
             // Convert UEFI types to r-efi compatible types.
             let handle = uefi::boot::image_handle().as_ptr();
 
-            bench_start(handle as r_efi::efi::Handle).unwrap_or_else(|e| {
+            bench_start_with_config(handle as r_efi::efi::Handle, &config, &ConsoleOutputSink).unwrap_or_else(|e| {
                 log::error!("Services Benchmark Test failed: {:?}", e);
             });
 
@@ -9,73 +9,1971 @@ use r_efi::efi;
 use rolling_stats::Stats;
 
 use crate::{
+    alloc::vec::Vec,
     bench::{
-        controller::bench_connect_controller,
+        baseline::bench_boot_services_init_cost,
+        controller::{
+            bench_connect_controller, bench_connect_controller_driver_unsupported,
+            bench_connect_controller_multi_image_driver, bench_connect_controller_wildcard,
+            bench_connect_controller_with_children, bench_connect_controller_with_remaining_path,
+        },
         event::{
-            bench_check_event_signaled, bench_check_event_unsignaled, bench_close_event, bench_create_event,
-            bench_signal_event, bench_signal_event_group,
+            bench_callback_dispatch_latency, bench_check_event_pending_notify, bench_check_event_poll_until_signaled,
+            bench_check_event_signaled, bench_check_event_unsignaled, bench_close_event,
+            bench_close_event_deferred_from_tpl_high, bench_close_event_signaled, bench_close_event_timer_pending,
+            bench_close_event_unsignaled, bench_create_event, bench_create_event_ex_no_notify,
+            bench_create_event_ex_null_group, bench_create_event_ex_with_notify, bench_create_event_notify_signal,
+            bench_create_event_remote_context, bench_create_event_runtime, bench_create_event_timer,
+            bench_create_event_timer_notify_signal, bench_event_dispatch_latency, bench_signal_event,
+            bench_signal_event_context_callback, bench_signal_event_from_tpl_high, bench_signal_event_from_tpl_notify,
+            bench_signal_event_group, bench_signal_event_group_fixed_100, bench_signal_event_group_mixed_tpl,
+            bench_signal_event_notify_signal, bench_signal_event_notify_tpl_notify, bench_signal_event_resignal,
+            bench_signal_event_tpl_application, bench_signal_event_tpl_callback,
+        },
+        image::{
+            bench_load_image, bench_load_image_boot_policy_true, bench_load_image_from_filesystem,
+            bench_load_image_large_header, bench_load_image_with_sections, bench_start_failed_image,
+            bench_start_image_and_exit, bench_start_image_and_exit_minimal_call,
         },
-        image::{bench_load_image, bench_start_image_and_exit},
         memory::{
-            bench_allocate_pages, bench_allocate_pool, bench_copy_mem, bench_free_pages, bench_free_pool,
-            bench_get_memory_map, bench_set_mem,
+            bench_alloc_free_throughput, bench_alloc_pool_then_get_memory_map, bench_allocate_pages,
+            bench_allocate_pages_at_address, bench_allocate_pages_below_1mb, bench_allocate_pool,
+            bench_allocate_pool_clean_page, bench_allocate_pool_fragmented, bench_allocate_pool_page_boundary,
+            bench_allocate_pool_zero_bytes, bench_copy_mem, bench_copy_mem_4k, bench_copy_mem_different_pages,
+            bench_copy_mem_same_page, bench_free_pages, bench_free_pages_below_1mb, bench_free_pool,
+            bench_free_pool_wrong_pointer, bench_get_memory_map, bench_get_memory_map_after_free,
+            bench_get_memory_map_key_change, bench_rust_copy_mem, bench_rust_set_mem, bench_set_mem,
+            bench_set_mem_page_aligned,
+        },
+        misc::{
+            bench_calculate_crc32, bench_calculate_crc32_4chunks, bench_calculate_crc32_all_ones, bench_crc32_scaling,
+            bench_install_configuration_table, bench_install_configuration_table_nth, bench_remove_configuration_table,
         },
-        misc::{bench_calculate_crc32, bench_install_configuration_table},
         protocol::{
-            bench_close_protocol, bench_handle_protocol, bench_install_protocol_interface, bench_locate_device_path,
-            bench_open_protocol, bench_open_protocol_information, bench_protocols_per_handle,
-            bench_register_protocol_notify, bench_reinstall_protocol_interface, bench_uninstall_protocol_interface,
+            bench_close_protocol, bench_close_protocol_get_protocol, bench_handle_protocol,
+            bench_handle_protocol_multiple_on_same_handle, bench_handle_protocol_not_found,
+            bench_install_protocol_interface, bench_install_protocol_interface_existing_handle,
+            bench_install_protocol_on_n_protocol_handle, bench_install_protocol_recycle_handle,
+            bench_install_then_reinstall_same_guid, bench_locate_all_handles, bench_locate_device_path,
+            bench_locate_device_path_multi_node, bench_locate_device_path_not_found, bench_locate_handle_by_protocol_n,
+            bench_locate_handle_by_register_notify, bench_locate_handle_not_found, bench_locate_protocol,
+            bench_locate_protocol_builtin, bench_locate_protocol_by_key, bench_open_all_protocols_on_image_handle,
+            bench_open_close_protocol_at_tpl_callback, bench_open_close_roundtrip, bench_open_protocol,
+            bench_open_protocol_by_child_controller, bench_open_protocol_cold, bench_open_protocol_cold_cache,
+            bench_open_protocol_exclusive, bench_open_protocol_information, bench_open_protocol_information_all,
+            bench_open_protocol_information_n_entries, bench_open_protocol_information_with_free,
+            bench_open_protocol_nth_consumer, bench_open_protocol_warm, bench_open_protocol_warm_cache,
+            bench_protocols_per_handle, bench_protocols_per_handle_fresh, bench_protocols_per_handle_large,
+            bench_protocols_per_handle_post_thrash, bench_register_protocol_notify, bench_register_protocol_notify_nth,
+            bench_register_protocol_notify_unknown_guid, bench_reinstall_protocol_interface,
+            bench_reinstall_with_consumers, bench_reinstall_with_m_listeners, bench_uninstall_protocol_interface,
+            bench_uninstall_protocol_interface_non_last, bench_uninstall_protocol_with_consumers,
         },
-        tpl::{bench_raise_tpl, bench_restore_tpl},
+        tpl::{bench_raise_tpl, bench_restore_tpl, bench_tpl_nested_depth_n, bench_tpl_toggle_frequency},
     },
+    config::BenchConfig,
     error::BenchError,
 };
 
 // A BenchFn returns total cycles for one call
 // Takes in number of calls to make to measured fn
-type BenchFn = fn(efi::Handle, usize) -> Result<Stats<f64>, BenchError>;
+pub(crate) type BenchFn = fn(efi::Handle, usize, &BenchConfig) -> Result<Measurements, BenchError>;
+
+/// Number of iterations recorded before outlier detection starts, as the running standard deviation of the first few
+/// iterations is not meaningful.
+const MIN_OUTLIER_SAMPLES: usize = 10;
+
+/// Cycle counts measured by a benchmark.
+pub(crate) struct Measurements {
+    pub(crate) stats: Stats<f64>,
+    /// Number of iterations flagged as outliers.
+    pub(crate) outliers: usize,
+    outlier_threshold_factor: f64,
+    exclude_outliers: bool,
+    /// Per-iteration cycle counts. Only recorded when `BenchConfig::verbose_samples` is set.
+    pub(crate) samples: Option<Vec<f64>>,
+    /// A secondary quantity observed alongside the cycle counts, such as an allocation size.
+    pub(crate) aux: Option<AuxStats>,
+    /// Operations completed per millisecond by a throughput benchmark, reported as a row of its own.
+    pub(crate) throughput: Option<AuxStats>,
+    /// Cycle counts of a benchmark that sweeps a parameter, such as a buffer size, reported as one row per value.
+    pub(crate) series: Option<SeriesStats>,
+}
+
+/// Statistics of a secondary quantity observed by a benchmark, reported next to its cycle counts.
+pub(crate) struct AuxStats {
+    /// Short description of the quantity, including its unit.
+    pub(crate) label: &'static str,
+    pub(crate) stats: Stats<f64>,
+}
+
+/// Statistics of the cycle counts measured for each value of a parameter swept by a benchmark.
+pub(crate) struct SeriesStats {
+    /// Unit of the parameter, used in the row names.
+    pub(crate) unit: &'static str,
+    /// The parameter values and the statistics of their cycle counts, in the order they were first measured.
+    pub(crate) points: Vec<(usize, Stats<f64>)>,
+}
+
+impl Measurements {
+    /// Creates an empty set of measurements for a benchmark of `num_calls` iterations, recorded as set in `config`.
+    /// The sample buffer is allocated up front so that recording does not allocate inside the timed region.
+    pub(crate) fn new(num_calls: usize, config: &BenchConfig) -> Self {
+        let samples = config.verbose_samples.then(|| Vec::with_capacity(num_calls));
+        Self {
+            stats: Stats::new(),
+            outliers: 0,
+            outlier_threshold_factor: config.outlier_threshold_factor,
+            exclude_outliers: config.exclude_outliers,
+            samples,
+            aux: None,
+            throughput: None,
+            series: None,
+        }
+    }
+
+    /// Adds the iterations measured in `other` to these measurements.
+    pub(crate) fn merge(&mut self, other: Measurements) {
+        self.stats = self.stats.merge(&other.stats);
+        self.outliers += other.outliers;
+        if let (Some(samples), Some(other_samples)) = (self.samples.as_mut(), other.samples) {
+            samples.extend(other_samples);
+        }
+        match (self.aux.as_mut(), other.aux) {
+            (Some(aux), Some(other_aux)) => aux.stats = aux.stats.merge(&other_aux.stats),
+            (None, other_aux) => self.aux = other_aux,
+            (Some(_), None) => (),
+        }
+        match (self.throughput.as_mut(), other.throughput) {
+            (Some(throughput), Some(other_throughput)) => {
+                throughput.stats = throughput.stats.merge(&other_throughput.stats)
+            }
+            (None, other_throughput) => self.throughput = other_throughput,
+            (Some(_), None) => (),
+        }
+        match (self.series.as_mut(), other.series) {
+            (Some(series), Some(other_series)) => {
+                for (value, other_stats) in other_series.points {
+                    match series.points.iter_mut().find(|(point_value, _)| *point_value == value) {
+                        Some((_, stats)) => *stats = stats.merge(&other_stats),
+                        None => series.points.push((value, other_stats)),
+                    }
+                }
+            }
+            (None, other_series) => self.series = other_series,
+            (Some(_), None) => (),
+        }
+    }
+
+    /// Returns the coefficient of variation (standard deviation / mean) of the cycle counts.
+    pub(crate) fn coefficient_of_variation(&self) -> f64 {
+        if self.stats.mean == 0.0 { 0.0 } else { self.stats.std_dev / self.stats.mean }
+    }
+
+    /// Returns whether `cycles` deviates from the running mean by more than the outlier threshold.
+    ///
+    /// The deviation of the iteration itself is compared rather than the change it causes in the running mean. That
+    /// change is the deviation divided by the iteration count, so comparing it against the same threshold would stop
+    /// flagging anything after a few iterations. Iterations are never flagged while all previous ones measured the same
+    /// cycle count, as a zero standard deviation would otherwise flag any differing iteration.
+    fn is_outlier(&self, cycles: f64) -> bool {
+        self.stats.count >= MIN_OUTLIER_SAMPLES
+            && self.stats.std_dev > 0.0
+            && (cycles - self.stats.mean).abs() > self.outlier_threshold_factor * self.stats.std_dev
+    }
+
+    /// Records a value of the secondary quantity described by `label` for a single iteration.
+    pub(crate) fn update_aux(&mut self, label: &'static str, value: f64) {
+        self.aux.get_or_insert_with(|| AuxStats { label, stats: Stats::new() }).stats.update(value);
+    }
+
+    /// Records the operations per millisecond, described by `label`, achieved in a single iteration.
+    pub(crate) fn update_throughput(&mut self, label: &'static str, ops_per_ms: f64) {
+        self.throughput.get_or_insert_with(|| AuxStats { label, stats: Stats::new() }).stats.update(ops_per_ms);
+    }
+
+    /// Records the cycle count of a single iteration for the parameter `value`, measured in `unit`.
+    /// Series points are not checked for outliers.
+    pub(crate) fn update_series(&mut self, unit: &'static str, value: usize, cycles: f64) {
+        let series = self.series.get_or_insert_with(|| SeriesStats { unit, points: Vec::new() });
+        match series.points.iter_mut().find(|(point_value, _)| *point_value == value) {
+            Some((_, stats)) => stats.update(cycles),
+            None => {
+                let mut stats = Stats::new();
+                stats.update(cycles);
+                series.points.push((value, stats));
+            }
+        }
+    }
+
+    /// Records the cycle count of a single iteration.
+    /// An iteration more than the outlier threshold of standard deviations away from the running mean is counted as an
+    /// outlier, and left out of the statistics if outliers are excluded.
+    pub(crate) fn update(&mut self, cycles: f64) {
+        if self.is_outlier(cycles) {
+            self.outliers += 1;
+            if self.exclude_outliers {
+                return;
+            }
+        }
+        self.stats.update(cycles);
+        if let Some(samples) = self.samples.as_mut() {
+            samples.push(cycles);
+        }
+    }
+}
 
 #[derive(Copy, Clone)]
 pub(crate) struct BenchFnWrapper {
     pub(crate) func: BenchFn,
     pub(crate) name: &'static str,
+    /// Group of boot services the benchmark belongs to.
+    pub(crate) category: &'static str,
+    /// One-line summary of what the benchmark measures.
+    pub(crate) description: &'static str,
+    /// Lowest UEFI specification revision, as `(major, minor)`, that provides the services used by the benchmark.
+    /// EFI 1.10, which added services such as `OpenProtocol` and `ConnectController`, is `(1, 1)`.
+    pub(crate) min_uefi_version: (u16, u16),
+    /// Whether the benchmark passes invalid input that may crash the firmware or trip a debug assertion, instead of
+    /// being rejected with an error status. Such benchmarks only run when `BenchConfig::run_fault_probes` is set.
+    pub(crate) fault_probe: bool,
+}
+
+impl BenchFnWrapper {
+    /// Returns whether the firmware's UEFI revision, as found in a table header's `revision` field, can run this
+    /// benchmark.
+    pub(crate) fn is_supported_by(&self, uefi_revision: u32) -> bool {
+        // The low 16 bits hold the minor revision times ten plus the patch level, e.g. 2.3.1 is `0x0002_001F`.
+        let revision = ((uefi_revision >> 16) as u16, (uefi_revision & 0xffff) as u16 / 10);
+        revision >= self.min_uefi_version
+    }
+}
+
+/// Ratio of the mean cycles per operation of two benchmarks in the same section, reported after that section.
+pub(crate) struct BenchRatio {
+    pub(crate) name: &'static str,
+    pub(crate) numerator: &'static str,
+    pub(crate) denominator: &'static str,
 }
 
-pub static BENCH_FNS: [(BenchFnWrapper, usize); 30] = [
+pub(crate) static BENCH_RATIOS: [BenchRatio; 2] = [
+    // `handle_protocol` is specified as an `open_protocol` with `GET_PROTOCOL` semantics, so a ratio well above 1.0
+    // points to unnecessary wrapper cost in the firmware's `handle_protocol`.
+    BenchRatio {
+        name: "handle_protocol overhead ratio",
+        numerator: "handle_protocol",
+        denominator: "open_protocol_warm",
+    },
+    // Both benchmarks create the same ungrouped event, so a ratio above 1.0 is the cost of the `create_event_ex`
+    // event group handling.
+    BenchRatio {
+        name: "create_event_ex overhead ratio",
+        numerator: "create_event_ex_null_group",
+        denominator: "create_event",
+    },
+];
+
+pub static BENCH_FNS: [(BenchFnWrapper, usize); 154] = [
+    /* BASELINE */
+    (
+        BenchFnWrapper {
+            func: bench_boot_services_init_cost,
+            name: "boot_services_init_cost",
+            category: "Baseline",
+            description: "Initializes a StandardBootServices wrapper",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
     /* CONTROLLER SERVICES */
-    (BenchFnWrapper { func: bench_connect_controller, name: "connect_controller" }, 100),
+    (
+        BenchFnWrapper {
+            func: bench_connect_controller,
+            name: "connect_controller",
+            category: "Controller",
+            description: "Connects a mock driver to a controller",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_connect_controller_wildcard,
+            name: "connect_controller_wildcard",
+            category: "Controller",
+            description: "Connects a controller without a driver list",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_connect_controller_multi_image_driver,
+            name: "connect_controller_multi_image_driver",
+            category: "Controller",
+            description: "Connects two mock drivers owned by the same image to a controller",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_connect_controller_driver_unsupported,
+            name: "connect_controller_driver_unsupported",
+            category: "Controller",
+            description: "Connects a controller whose only driver rejects it in Supported",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_connect_controller_with_remaining_path,
+            name: "connect_controller_with_remaining_path",
+            category: "Controller",
+            description: "Connects a controller with a remaining device path",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_connect_controller_with_children::<0>,
+            name: "connect_controller_with_0_children",
+            category: "Controller",
+            description: "Connects a controller that has no child handles",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_connect_controller_with_children::<5>,
+            name: "connect_controller_with_5_children",
+            category: "Controller",
+            description: "Connects a controller that has 5 child handles",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_connect_controller_with_children::<20>,
+            name: "connect_controller_with_20_children",
+            category: "Controller",
+            description: "Connects a controller that has 20 child handles",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
     /* EVENT SERVICES */
-    (BenchFnWrapper { func: bench_check_event_signaled, name: "bench_check_event_signaled" }, 10_000),
-    (BenchFnWrapper { func: bench_check_event_unsignaled, name: "bench_check_event_unsignaled" }, 10_000),
-    (BenchFnWrapper { func: bench_create_event, name: "create_event" }, 1000),
-    (BenchFnWrapper { func: bench_close_event, name: "close_event" }, 1000),
-    (BenchFnWrapper { func: bench_signal_event, name: "signal_event" }, 100_000),
-    (BenchFnWrapper { func: bench_signal_event_group, name: "signal_event_group" }, 100),
+    (
+        BenchFnWrapper {
+            func: bench_check_event_signaled,
+            name: "bench_check_event_signaled",
+            category: "Event",
+            description: "Checks an already-signaled event (fast path)",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_check_event_unsignaled,
+            name: "bench_check_event_unsignaled",
+            category: "Event",
+            description: "Checks an unsignaled event (slow path)",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_check_event_pending_notify,
+            name: "check_event_pending_notify",
+            category: "Event",
+            description: "Checks an event whose notify function is queued but not dispatched",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_check_event_poll_until_signaled,
+            name: "check_event_poll_until_signaled",
+            category: "Event",
+            description: "Polls check_event until the notify function signals the event",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_create_event,
+            name: "create_event",
+            category: "Event",
+            description: "Creates a NOTIFY_WAIT event",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_create_event_notify_signal,
+            name: "create_event_notify_signal",
+            category: "Event",
+            description: "Creates a NOTIFY_SIGNAL event",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_create_event_timer,
+            name: "create_event_timer",
+            category: "Event",
+            description: "Creates a TIMER event without a notify function",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_create_event_timer_notify_signal,
+            name: "create_event_timer_notify_signal",
+            category: "Event",
+            description: "Creates a TIMER | NOTIFY_SIGNAL event",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_create_event_runtime,
+            name: "create_event_runtime",
+            category: "Event",
+            description: "Creates a RUNTIME event",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_create_event_ex_no_notify,
+            name: "create_event_ex_no_notify",
+            category: "Event",
+            description: "Creates a grouped timer event without a notify function",
+            min_uefi_version: (2, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_create_event_ex_with_notify,
+            name: "create_event_ex_with_notify",
+            category: "Event",
+            description: "Creates a grouped timer event with a notify function",
+            min_uefi_version: (2, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_create_event_ex_null_group,
+            name: "create_event_ex_null_group",
+            category: "Event",
+            description: "Creates a NOTIFY_WAIT event with a null event group GUID",
+            min_uefi_version: (2, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_close_event,
+            name: "close_event",
+            category: "Event",
+            description: "Closes an unsignaled NOTIFY_WAIT event",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_close_event_signaled,
+            name: "close_event_signaled",
+            category: "Event",
+            description: "Closes a signaled event whose notify function is still queued",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_close_event_deferred_from_tpl_high,
+            name: "close_event_deferred_from_tpl_high",
+            category: "Event",
+            description: "Closes a TPL_NOTIFY event signaled at TPL_HIGH_LEVEL before it is dispatched",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_close_event_unsignaled,
+            name: "close_event_unsignaled",
+            category: "Event",
+            description: "Closes an unsignaled NOTIFY_SIGNAL event at TPL_NOTIFY",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_close_event_timer_pending,
+            name: "close_event_timer_pending",
+            category: "Event",
+            description: "Closes a timer event with a pending timer",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event,
+            name: "signal_event",
+            category: "Event",
+            description: "Signals a NOTIFY_WAIT event",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_notify_signal,
+            name: "signal_event_notify_signal",
+            category: "Event",
+            description: "Signals a NOTIFY_SIGNAL event with a no-op notify function",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_tpl_application,
+            name: "signal_event_tpl_application",
+            category: "Event",
+            description: "Signals a TPL_CALLBACK notify event from TPL_APPLICATION",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_tpl_callback,
+            name: "signal_event_tpl_callback",
+            category: "Event",
+            description: "Signals a TPL_CALLBACK notify event from TPL_CALLBACK",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_from_tpl_notify,
+            name: "signal_event_from_tpl_notify",
+            category: "Event",
+            description: "Signals a TPL_CALLBACK notify event from TPL_NOTIFY",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_from_tpl_high,
+            name: "signal_event_from_tpl_high",
+            category: "Event",
+            description: "Signals a TPL_CALLBACK notify event from TPL_HIGH_LEVEL",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_notify_tpl_notify,
+            name: "signal_event_notify_tpl_notify",
+            category: "Event",
+            description: "Signals a TPL_NOTIFY notify event from TPL_APPLICATION",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_resignal,
+            name: "signal_event_resignal",
+            category: "Event",
+            description: "Signals an event that is already signaled",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_context_callback,
+            name: "signal_event_context_callback",
+            category: "Event",
+            description: "Signals an event whose notify function updates its context",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_callback_dispatch_latency,
+            name: "callback_dispatch_latency",
+            category: "Event",
+            description: "Latency from signal_event to notify function entry",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_event_dispatch_latency,
+            name: "event_dispatch_latency",
+            category: "Event",
+            description: "Latency from signal_event to TPL_CALLBACK notify function entry",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_create_event_remote_context,
+            name: "create_event_remote_context",
+            category: "Event",
+            description: "Dispatch latency with the context above 4 GiB",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_group,
+            name: "signal_event_group",
+            category: "Event",
+            description: "Signals an event group that grows with every iteration",
+            min_uefi_version: (2, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_group_fixed_100,
+            name: "signal_event_group_fixed_100",
+            category: "Event",
+            description: "Signals an event group of exactly 100 events",
+            min_uefi_version: (2, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_group_mixed_tpl::<0>,
+            name: "signal_event_group_mixed_tpl_0",
+            category: "Event",
+            description: "Signals a 100-event group with 0 events at TPL_CALLBACK",
+            min_uefi_version: (2, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_group_mixed_tpl::<50>,
+            name: "signal_event_group_mixed_tpl_50",
+            category: "Event",
+            description: "Signals a 100-event group with 50 events at TPL_CALLBACK",
+            min_uefi_version: (2, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_signal_event_group_mixed_tpl::<100>,
+            name: "signal_event_group_mixed_tpl_100",
+            category: "Event",
+            description: "Signals a 100-event group with 100 events at TPL_CALLBACK",
+            min_uefi_version: (2, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
     /* IMAGE SERVICES */
-    (BenchFnWrapper { func: bench_start_image_and_exit, name: "start_image, exit" }, 100),
-    (BenchFnWrapper { func: bench_load_image, name: "load_image" }, 100),
+    (
+        BenchFnWrapper {
+            func: bench_start_image_and_exit,
+            name: "start_image, exit",
+            category: "Image",
+            description: "Starts a no-op image that exits immediately",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_start_image_and_exit_minimal_call,
+            name: "start_image, exit (minimal call)",
+            category: "Image",
+            description: "Starts an image that allocates and frees pool before exiting",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_start_failed_image,
+            name: "start_image, exit (failed)",
+            category: "Image",
+            description: "Starts an image that returns EFI_ABORTED from its entry point",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_load_image,
+            name: "load_image",
+            category: "Image",
+            description: "Loads a no-op image from a memory buffer",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_load_image_boot_policy_true,
+            name: "load_image_boot_policy_true",
+            category: "Image",
+            description: "Loads a no-op image with boot_policy set",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_load_image_large_header,
+            name: "load_image_large_header",
+            category: "Image",
+            description: "Loads a no-op image with 32 extra sections",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_load_image_with_sections,
+            name: "load_image_with_sections",
+            category: "Image",
+            description: "Loads an image with .data and .bss sections",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_load_image_from_filesystem,
+            name: "load_image_from_filesystem",
+            category: "Image",
+            description: "Loads a no-op image from a file system device path",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        50,
+    ),
     /* MEMORY SERVICES */
-    (BenchFnWrapper { func: bench_allocate_pages, name: "allocate_pages" }, 1000),
-    (BenchFnWrapper { func: bench_allocate_pool, name: "allocate_pool" }, 10_000),
-    (BenchFnWrapper { func: bench_free_pages, name: "free_pages" }, 100),
-    (BenchFnWrapper { func: bench_free_pool, name: "free_pool" }, 10_000),
-    (BenchFnWrapper { func: bench_copy_mem, name: "copy_mem" }, 10),
-    (BenchFnWrapper { func: bench_set_mem, name: "set_mem" }, 10),
-    (BenchFnWrapper { func: bench_get_memory_map, name: "get_memory_map" }, 10),
+    (
+        BenchFnWrapper {
+            func: bench_allocate_pages,
+            name: "allocate_pages",
+            category: "Memory",
+            description: "Allocates a single page",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_allocate_pages_at_address,
+            name: "allocate_pages_at_address",
+            category: "Memory",
+            description: "Allocates a single page at a fixed physical address",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_allocate_pages_below_1mb,
+            name: "allocate_pages_below_1mb",
+            category: "Memory",
+            description: "Allocates a single page below 1 MiB with a maximum address",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_allocate_pool,
+            name: "allocate_pool",
+            category: "Memory",
+            description: "Allocates a 1 KiB pool buffer",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_allocate_pool_zero_bytes,
+            name: "allocate_pool_zero_bytes",
+            category: "Memory",
+            description: "Allocates a zero-byte pool",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_allocate_pool_fragmented,
+            name: "allocate_pool_fragmented",
+            category: "Memory",
+            description: "Allocates a 64-byte pool buffer from a fragmented pool",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_allocate_pool_page_boundary,
+            name: "allocate_pool_page_boundary",
+            category: "Memory",
+            description: "Allocates pool that no longer fits in the pool page of the previous allocation",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_allocate_pool_clean_page,
+            name: "allocate_pool_clean_page",
+            category: "Memory",
+            description: "Allocates pool right after a page-crossing allocation, from the same page",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_free_pages,
+            name: "free_pages",
+            category: "Memory",
+            description: "Frees a single page",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_free_pages_below_1mb,
+            name: "free_pages_below_1mb",
+            category: "Memory",
+            description: "Frees a single page allocated below 1 MiB",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_free_pool,
+            name: "free_pool",
+            category: "Memory",
+            description: "Frees a 1 KiB pool buffer",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_free_pool_wrong_pointer,
+            name: "free_pool_wrong_pointer",
+            category: "Memory",
+            description: "Frees a page allocation with FreePool",
+            min_uefi_version: (1, 0),
+            fault_probe: true,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_alloc_free_throughput,
+            name: "alloc_free_throughput",
+            category: "Memory",
+            description: "Pool allocate/free pairs completed in a 10 ms window",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_copy_mem,
+            name: "copy_mem",
+            category: "Memory",
+            description: "Copies 8 bytes with CopyMem",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_copy_mem_4k,
+            name: "copy_mem_4k",
+            category: "Memory",
+            description: "Copies 4 KiB with CopyMem",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_copy_mem_same_page,
+            name: "copy_mem_same_page",
+            category: "Memory",
+            description: "Copies 2 KiB within one page with CopyMem",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_copy_mem_different_pages,
+            name: "copy_mem_different_pages",
+            category: "Memory",
+            description: "Copies 2 KiB between two pages with CopyMem",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_rust_copy_mem,
+            name: "rust_copy_mem",
+            category: "Memory",
+            description: "Copies 4 KiB with copy_nonoverlapping, bypassing the firmware",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_set_mem,
+            name: "set_mem",
+            category: "Memory",
+            description: "Fills a 128-byte stack buffer with SetMem",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_set_mem_page_aligned,
+            name: "set_mem_page_aligned",
+            category: "Memory",
+            description: "Fills a page-aligned 4 KiB page with SetMem",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_rust_set_mem,
+            name: "rust_set_mem",
+            category: "Memory",
+            description: "Fills a 128-byte buffer with write_bytes, bypassing the firmware",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_get_memory_map,
+            name: "get_memory_map",
+            category: "Memory",
+            description: "Retrieves the memory map",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_get_memory_map_after_free,
+            name: "get_memory_map_after_free",
+            category: "Memory",
+            description: "Retrieves the memory map after page allocations and frees",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_get_memory_map_key_change,
+            name: "get_memory_map_key_change",
+            category: "Memory",
+            description: "Gets the memory map after allocating and after freeing a page",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_alloc_pool_then_get_memory_map,
+            name: "alloc_pool_then_get_memory_map",
+            category: "Memory",
+            description: "Allocates pool and retrieves the memory map as one pair",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
     /* MISC SERVICES */
-    (BenchFnWrapper { func: bench_calculate_crc32, name: "calculate_crc32" }, 100),
-    (BenchFnWrapper { func: bench_install_configuration_table, name: "install_configuration_table" }, 10),
+    (
+        BenchFnWrapper {
+            func: bench_calculate_crc32,
+            name: "calculate_crc32",
+            category: "Misc",
+            description: "Calculates the CRC32 of a 128-byte buffer",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_calculate_crc32_all_ones,
+            name: "calculate_crc32_all_ones",
+            category: "Misc",
+            description: "Calculates the CRC32 of a 128-byte buffer of 0xFF bytes",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_calculate_crc32_4chunks,
+            name: "calculate_crc32_4chunks",
+            category: "Misc",
+            description: "Calculates the CRC32 of four 32-byte chunks, one call each",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_crc32_scaling,
+            name: "crc32_scaling",
+            category: "Misc",
+            description: "Calculates the CRC32 of buffers from 16 bytes to 64 KiB",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_configuration_table,
+            name: "install_configuration_table",
+            category: "Misc",
+            description: "Installs and removes a configuration table",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_configuration_table_nth::<1>,
+            name: "install_configuration_table_1st",
+            category: "Misc",
+            description: "Installs a configuration table as the 1st dummy table",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_configuration_table_nth::<10>,
+            name: "install_configuration_table_10th",
+            category: "Misc",
+            description: "Installs a configuration table as the 10th dummy table",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_configuration_table_nth::<50>,
+            name: "install_configuration_table_50th",
+            category: "Misc",
+            description: "Installs a configuration table as the 50th dummy table",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_remove_configuration_table,
+            name: "remove_configuration_table",
+            category: "Misc",
+            description: "Removes a configuration table with a NULL pointer",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
     /* PROTOCOL SERVICES */
-    (BenchFnWrapper { func: bench_install_protocol_interface, name: "install_protocol_interface" }, 100),
-    (BenchFnWrapper { func: bench_open_protocol, name: "open_protocol" }, 10_000),
-    (BenchFnWrapper { func: bench_handle_protocol, name: "handle_protocol" }, 10_000),
-    (BenchFnWrapper { func: bench_close_protocol, name: "close_protocol" }, 100),
-    (BenchFnWrapper { func: bench_locate_device_path, name: "locate_device_path" }, 100),
-    (BenchFnWrapper { func: bench_open_protocol_information, name: "open_protocol_information" }, 100),
-    (BenchFnWrapper { func: bench_protocols_per_handle, name: "protocols_per_handle" }, 100),
-    (BenchFnWrapper { func: bench_register_protocol_notify, name: "register_protocol_notify" }, 10),
-    (BenchFnWrapper { func: bench_reinstall_protocol_interface, name: "reinstall_protocol_interface" }, 100),
-    (BenchFnWrapper { func: bench_uninstall_protocol_interface, name: "uninstall_protocol_interface" }, 10),
-    (BenchFnWrapper { func: bench_raise_tpl, name: "raise_tpl" }, 1_000_000),
-    (BenchFnWrapper { func: bench_restore_tpl, name: "restore_tpl" }, 1_000_000),
+    (
+        BenchFnWrapper {
+            func: bench_install_protocol_interface,
+            name: "install_protocol_interface",
+            category: "Protocol",
+            description: "Installs a protocol on a new handle",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_protocol_interface_existing_handle,
+            name: "install_protocol_interface_existing_handle",
+            category: "Protocol",
+            description: "Installs a protocol on an existing handle",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_protocol_recycle_handle,
+            name: "install_protocol_recycle_handle",
+            category: "Protocol",
+            description: "Installs a protocol on a freed handle value",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_protocol_on_n_protocol_handle::<1>,
+            name: "install_protocol_on_1_protocol_handle",
+            category: "Protocol",
+            description: "Installs a protocol on a handle with 1 protocol",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_protocol_on_n_protocol_handle::<10>,
+            name: "install_protocol_on_10_protocol_handle",
+            category: "Protocol",
+            description: "Installs a protocol on a handle with 10 protocols",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_protocol_on_n_protocol_handle::<20>,
+            name: "install_protocol_on_20_protocol_handle",
+            category: "Protocol",
+            description: "Installs a protocol on a handle with 20 protocols",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_protocol_on_n_protocol_handle::<50>,
+            name: "install_protocol_on_50_protocol_handle",
+            category: "Protocol",
+            description: "Installs a protocol on a handle with 50 protocols",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_protocol_on_n_protocol_handle::<100>,
+            name: "install_protocol_on_100_protocol_handle",
+            category: "Protocol",
+            description: "Installs a protocol on a handle with 100 protocols",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_install_then_reinstall_same_guid,
+            name: "install_then_reinstall_same_guid",
+            category: "Protocol",
+            description: "Fails to install a protocol that is already on the handle",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol,
+            name: "open_protocol",
+            category: "Protocol",
+            description: "Opens a protocol BY_DRIVER",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_cold,
+            name: "open_protocol_cold",
+            category: "Protocol",
+            description: "Opens a protocol GET_PROTOCOL right after closing it",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_warm,
+            name: "open_protocol_warm",
+            category: "Protocol",
+            description: "Opens a protocol GET_PROTOCOL that is already open",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_warm_cache,
+            name: "open_protocol_warm_cache",
+            category: "Protocol",
+            description: "Opens a protocol BY_DRIVER right after opening the same handle",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_cold_cache,
+            name: "open_protocol_cold_cache",
+            category: "Protocol",
+            description: "Opens a protocol BY_DRIVER right after opening a different handle",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_by_child_controller,
+            name: "open_protocol_by_child_controller",
+            category: "Protocol",
+            description: "Opens a protocol BY_CHILD_CONTROLLER for a child handle",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_nth_consumer::<0>,
+            name: "open_protocol_after_0_consumers",
+            category: "Protocol",
+            description: "Opens a protocol BY_DRIVER with no other consumers",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_nth_consumer::<5>,
+            name: "open_protocol_after_5_consumers",
+            category: "Protocol",
+            description: "Opens a protocol BY_DRIVER with 5 other consumers",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_nth_consumer::<20>,
+            name: "open_protocol_after_20_consumers",
+            category: "Protocol",
+            description: "Opens a protocol BY_DRIVER with 20 other consumers",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_close_roundtrip,
+            name: "open_close_roundtrip",
+            category: "Protocol",
+            description: "Opens and closes a protocol as one timed pair",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_close_protocol_at_tpl_callback,
+            name: "open_close_protocol_at_tpl_callback",
+            category: "Protocol",
+            description: "Opens and closes a protocol at TPL_CALLBACK",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_exclusive,
+            name: "open_protocol_exclusive",
+            category: "Protocol",
+            description: "Opens a protocol EXCLUSIVE",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_all_protocols_on_image_handle,
+            name: "open_all_protocols_on_image_handle",
+            category: "Protocol",
+            description: "Enumerates and opens every protocol on the image handle",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_handle_protocol,
+            name: "handle_protocol",
+            category: "Protocol",
+            description: "Queries a protocol with HandleProtocol",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_handle_protocol_not_found,
+            name: "handle_protocol_not_found",
+            category: "Protocol",
+            description: "Queries a protocol that is not on the handle",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_handle_protocol_multiple_on_same_handle,
+            name: "handle_protocol_multiple_on_same_handle",
+            category: "Protocol",
+            description: "Queries two protocols on the same handle back-to-back",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_close_protocol,
+            name: "close_protocol",
+            category: "Protocol",
+            description: "Closes a protocol opened BY_DRIVER",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_close_protocol_get_protocol,
+            name: "close_protocol_get_protocol",
+            category: "Protocol",
+            description: "Closes a protocol opened with GET_PROTOCOL",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_device_path,
+            name: "locate_device_path",
+            category: "Protocol",
+            description: "Locates the device path of the image's device",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_device_path_multi_node,
+            name: "locate_device_path_multi_node",
+            category: "Protocol",
+            description: "Locates a constructed two-node device path",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_device_path_not_found,
+            name: "locate_device_path_not_found",
+            category: "Protocol",
+            description: "Locates a device path that matches no handle",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_all_handles,
+            name: "locate_all_handles",
+            category: "Protocol",
+            description: "Locates every handle in the handle database",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_handle_by_protocol_n::<10>,
+            name: "locate_handle_by_protocol_10",
+            category: "Protocol",
+            description: "Locates handles by protocol with 10 matching handles",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_handle_by_protocol_n::<100>,
+            name: "locate_handle_by_protocol_100",
+            category: "Protocol",
+            description: "Locates handles by protocol with 100 matching handles",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_handle_by_protocol_n::<500>,
+            name: "locate_handle_by_protocol_500",
+            category: "Protocol",
+            description: "Locates handles by protocol with 500 matching handles",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_handle_not_found,
+            name: "locate_handle_not_found",
+            category: "Protocol",
+            description: "Locates handles by a protocol no handle supports",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_handle_by_register_notify,
+            name: "locate_handle_by_register_notify",
+            category: "Protocol",
+            description: "Locates handles through a protocol notify registration",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_protocol,
+            name: "locate_protocol",
+            category: "Protocol",
+            description: "Locates a test protocol",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_protocol_by_key,
+            name: "locate_protocol_by_key",
+            category: "Protocol",
+            description: "Locates a protocol through a notify registration key",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        1000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_locate_protocol_builtin,
+            name: "locate_protocol_builtin",
+            category: "Protocol",
+            description: "Locates the loaded image protocol",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_information,
+            name: "open_protocol_information",
+            category: "Protocol",
+            description: "Retrieves open information of the loaded image protocol",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_information_with_free,
+            name: "open_protocol_information_with_free",
+            category: "Protocol",
+            description: "Gets open protocol information and frees the returned buffer",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_information_all,
+            name: "open_protocol_information_all",
+            category: "Protocol",
+            description: "Gets open protocol information with a NULL GUID",
+            min_uefi_version: (1, 1),
+            fault_probe: true,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_information_n_entries::<0>,
+            name: "open_protocol_information_0_entries",
+            category: "Protocol",
+            description: "Retrieves open information of a protocol with 0 opens",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_information_n_entries::<5>,
+            name: "open_protocol_information_5_entries",
+            category: "Protocol",
+            description: "Retrieves open information of a protocol with 5 opens",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_open_protocol_information_n_entries::<50>,
+            name: "open_protocol_information_50_entries",
+            category: "Protocol",
+            description: "Retrieves open information of a protocol with 50 opens",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_protocols_per_handle,
+            name: "protocols_per_handle",
+            category: "Protocol",
+            description: "Enumerates the protocols on the image handle",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_protocols_per_handle_fresh,
+            name: "protocols_per_handle_fresh",
+            category: "Protocol",
+            description: "Enumerates the protocols on a handle with 1 protocol",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_protocols_per_handle_post_thrash,
+            name: "protocols_per_handle_post_thrash",
+            category: "Protocol",
+            description: "Enumerates the protocols on a handle after 1000 install/uninstall cycles",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_protocols_per_handle_large,
+            name: "protocols_per_handle_large",
+            category: "Protocol",
+            description: "Enumerates the protocols on a handle with 100 protocols",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_register_protocol_notify,
+            name: "register_protocol_notify",
+            category: "Protocol",
+            description: "Registers a protocol notify event",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_register_protocol_notify_unknown_guid,
+            name: "register_protocol_notify_unknown_guid",
+            category: "Protocol",
+            description: "Registers a protocol notify event for an unused GUID",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_register_protocol_notify_nth::<1>,
+            name: "register_protocol_notify_after_1_registrations",
+            category: "Protocol",
+            description: "Registers a protocol notify event for a GUID with 1 registration",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_register_protocol_notify_nth::<10>,
+            name: "register_protocol_notify_after_10_registrations",
+            category: "Protocol",
+            description: "Registers a protocol notify event for a GUID with 10 registrations",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_register_protocol_notify_nth::<50>,
+            name: "register_protocol_notify_after_50_registrations",
+            category: "Protocol",
+            description: "Registers a protocol notify event for a GUID with 50 registrations",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_reinstall_protocol_interface,
+            name: "reinstall_protocol_interface",
+            category: "Protocol",
+            description: "Reinstalls a protocol interface",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_reinstall_with_consumers::<1>,
+            name: "reinstall_with_1_consumer",
+            category: "Protocol",
+            description: "Reinstalls a protocol that has 1 consumer",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_reinstall_with_consumers::<5>,
+            name: "reinstall_with_5_consumers",
+            category: "Protocol",
+            description: "Reinstalls a protocol that has 5 consumers",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_reinstall_with_m_listeners::<0>,
+            name: "reinstall_with_0_listeners",
+            category: "Protocol",
+            description: "Reinstalls a protocol with 0 notify listeners",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_reinstall_with_m_listeners::<5>,
+            name: "reinstall_with_5_listeners",
+            category: "Protocol",
+            description: "Reinstalls a protocol with 5 notify listeners",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_reinstall_with_m_listeners::<20>,
+            name: "reinstall_with_20_listeners",
+            category: "Protocol",
+            description: "Reinstalls a protocol with 20 notify listeners",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_uninstall_protocol_interface,
+            name: "uninstall_protocol_interface",
+            category: "Protocol",
+            description: "Uninstalls a protocol interface",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_uninstall_protocol_interface_non_last,
+            name: "uninstall_protocol_interface_non_last",
+            category: "Protocol",
+            description: "Uninstalls one of two protocols on a handle",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        10,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_uninstall_protocol_with_consumers,
+            name: "uninstall_protocol_with_consumers",
+            category: "Protocol",
+            description: "Uninstalls a protocol that a driver has open BY_DRIVER",
+            min_uefi_version: (1, 1),
+            fault_probe: false,
+        },
+        10,
+    ),
+    /* TPL SERVICES */
+    (
+        BenchFnWrapper {
+            func: bench_raise_tpl,
+            name: "raise_tpl",
+            category: "TPL",
+            description: "Raises the TPL to TPL_HIGH_LEVEL",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1_000_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_restore_tpl,
+            name: "restore_tpl",
+            category: "TPL",
+            description: "Restores the TPL from rotating levels",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        1_000_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_tpl_nested_depth_n::<1>,
+            name: "tpl_nested_depth_1",
+            category: "TPL",
+            description: "Raises and restores the TPL at nesting depth 1",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_tpl_nested_depth_n::<2>,
+            name: "tpl_nested_depth_2",
+            category: "TPL",
+            description: "Raises and restores the TPL at nesting depth 2",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_tpl_nested_depth_n::<4>,
+            name: "tpl_nested_depth_4",
+            category: "TPL",
+            description: "Raises and restores the TPL at nesting depth 4",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100_000,
+    ),
+    (
+        BenchFnWrapper {
+            func: bench_tpl_toggle_frequency,
+            name: "tpl_toggle_frequency",
+            category: "TPL",
+            description: "TPL raise/restore pairs completed in a 1 ms window",
+            min_uefi_version: (1, 0),
+            fault_probe: false,
+        },
+        100,
+    ),
 ];
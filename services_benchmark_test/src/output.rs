@@ -0,0 +1,52 @@
+//! Destinations for benchmark results output.
+//!
+//! Copyright (c) Microsoft Corporation.
+//!
+//! SPDX-License-Identifier: Apache-2.0
+//!
+
+use crate::alloc::vec::Vec;
+
+/// A destination that the formatted benchmark results are written to.
+pub trait OutputSink {
+    /// Writes the given string to the sink.
+    fn write(&self, s: &str);
+}
+
+/// Writes output to the UEFI console (`ConOut`) of the System Table.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConsoleOutputSink;
+
+impl OutputSink for ConsoleOutputSink {
+    fn write(&self, s: &str) {
+        let Some(st_ptr) = uefi::table::system_table_raw() else {
+            return;
+        };
+        // SAFETY: The `uefi` crate only provides the System Table pointer once it has been initialized,
+        // at which point it is guaranteed to be valid.
+        let system_table = unsafe { &*st_ptr.as_ptr() };
+        let con_out = system_table.stdout;
+
+        if con_out.is_null() {
+            return;
+        }
+
+        // Convert the message to UTF-16 for UEFI console output.
+        let mut utf16_buffer: Vec<u16> = s.encode_utf16().collect();
+        utf16_buffer.push(0); // Null terminator.
+
+        // Call the UEFI console output function.
+        // SAFETY: `con_out` is a non-null protocol pointer provided by the valid System Table.
+        let output_string = unsafe { (*con_out).output_string };
+        // SAFETY: `con_out` is valid (see above) and `utf16_buffer` is a null-terminated UTF-16 string.
+        let _ = unsafe { output_string(con_out, utf16_buffer.as_ptr() as *mut u16) };
+    }
+}
+
+/// Discards all output. Useful for measuring the overhead of the benchmark runner itself.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NullOutputSink;
+
+impl OutputSink for NullOutputSink {
+    fn write(&self, _s: &str) {}
+}
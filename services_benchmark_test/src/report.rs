@@ -0,0 +1,682 @@
+//! Formatting of benchmark results into the supported output formats.
+//!
+//! Copyright (c) Microsoft Corporation.
+//!
+//! SPDX-License-Identifier: Apache-2.0
+//!
+
+use core::fmt::Write;
+
+use rolling_stats::Stats;
+
+use crate::{
+    alloc::{
+        format,
+        string::{String, ToString},
+        vec::Vec,
+    },
+    error::BenchError,
+    measure::{BENCH_FNS, BENCH_RATIOS, BenchRatio},
+};
+
+/// Outcome of a single benchmark in a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchStatus {
+    /// The benchmark ran and its statistics are valid.
+    Measured,
+    /// The benchmark was not run because the firmware does not meet its minimum UEFI version, it is a fault probe that
+    /// is not enabled, or the system lacks something it needs. The reason is in the notes.
+    Skipped,
+    /// The benchmark returned an error.
+    Failed,
+    /// The benchmark was listed but not run because `BenchConfig::dry_run` is set.
+    DryRun,
+}
+
+impl BenchStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            BenchStatus::Measured => "measured",
+            BenchStatus::Skipped => "skipped",
+            BenchStatus::Failed => "failed",
+            BenchStatus::DryRun => "dry_run",
+        }
+    }
+}
+
+/// Result of a single benchmark in a run.
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    /// Name of the benchmark, or of a point in the data series of a benchmark that measures one.
+    pub name: String,
+    /// Category of the benchmark, used to group the results into sections.
+    pub category: &'static str,
+    /// Outcome of the benchmark.
+    pub status: BenchStatus,
+    /// Cycle count statistics of the measured iterations. Only meaningful for `BenchStatus::Measured`.
+    pub stats: Stats<f64>,
+    /// Total time of the measured iterations in milliseconds.
+    pub total_time_ms: f64,
+    /// Number of measured iterations, or the registered iteration count for `BenchStatus::DryRun`.
+    pub num_calls: usize,
+    /// Number of iterations flagged as outliers.
+    pub outliers: usize,
+    /// Free-form notes for the Notes column, e.g. the benchmark's auxiliary measurement.
+    pub notes: String,
+    /// Cycle count of every measured iteration, when `BenchConfig::verbose_samples` is set.
+    pub samples: Option<Vec<f64>>,
+}
+
+impl BenchResult {
+    /// Creates a result with no statistics for a benchmark that did not produce measurements.
+    pub fn unmeasured(name: &str, category: &'static str, status: BenchStatus) -> Self {
+        Self {
+            name: name.to_string(),
+            category,
+            status,
+            stats: Stats::default(),
+            total_time_ms: 0.0,
+            num_calls: 0,
+            outliers: 0,
+            notes: String::new(),
+            samples: None,
+        }
+    }
+}
+
+/// The results of a benchmark run, which can be written as markdown, CSV, or JSON.
+#[derive(Debug, Clone)]
+pub struct PerfReport {
+    results: Vec<BenchResult>,
+}
+
+impl PerfReport {
+    /// Creates a report from the results of a run, in the order the benchmarks ran.
+    pub fn new(results: Vec<BenchResult>) -> Self {
+        Self { results }
+    }
+
+    /// Returns the results in the report.
+    pub fn results(&self) -> &[BenchResult] {
+        &self.results
+    }
+
+    /// Formats the report as the metadata table followed by the fixed-width results table, with the ratio rows at the
+    /// end of each section, and the histograms of the benchmarks that recorded samples.
+    pub fn as_markdown(&self) -> String {
+        let mut output_buf = String::new();
+        // Formatting into a `String` cannot fail.
+        let _ = self.write_markdown(&mut output_buf);
+        output_buf
+    }
+
+    /// Formats the report as CSV with a header row and one row per benchmark. The statistics columns are empty for
+    /// benchmarks that were not measured.
+    pub fn as_csv(&self) -> String {
+        let mut output_buf = String::new();
+        // Formatting into a `String` cannot fail.
+        let _ = self.write_csv(&mut output_buf);
+        output_buf
+    }
+
+    /// Formats the cycle count of every recorded iteration as CSV with a header row and one row per iteration, in the
+    /// order the iterations were measured. Only benchmarks run with `BenchConfig::verbose_samples` have rows.
+    pub fn samples_as_csv(&self) -> String {
+        let mut output_buf = String::new();
+        // Formatting into a `String` cannot fail.
+        let _ = self.write_samples_csv(&mut output_buf);
+        output_buf
+    }
+
+    /// Formats the report as a JSON object with a `results` array holding one object per benchmark and a `ratios`
+    /// array. The statistics are `null` for benchmarks that were not measured, and `samples` holds the cycle count of
+    /// every recorded iteration or is `null` if none were recorded.
+    pub fn as_json(&self) -> String {
+        let mut output_buf = String::new();
+        // Formatting into a `String` cannot fail.
+        let _ = self.write_json(&mut output_buf);
+        output_buf
+    }
+
+    fn write_markdown(&self, output_buf: &mut String) -> Result<(), BenchError> {
+        write_metadata_table(output_buf)?;
+        write_headers(output_buf)?;
+        for section in self.sections() {
+            for result in section {
+                match result.status {
+                    BenchStatus::Measured => write_result_row(
+                        output_buf,
+                        &result.name,
+                        result.stats.clone(),
+                        result.total_time_ms,
+                        result.num_calls,
+                        result.outliers,
+                        &result.notes,
+                    )?,
+                    BenchStatus::Skipped | BenchStatus::Failed => {
+                        let suffix = if result.status == BenchStatus::Skipped { " (Skipped)" } else { " (Failed)" };
+                        write_result_row(
+                            output_buf,
+                            (result.name.clone() + suffix).as_str(),
+                            Stats::default(),
+                            0.0,
+                            0,
+                            0,
+                            &result.notes,
+                        )?
+                    }
+                    BenchStatus::DryRun => {
+                        write_dry_run_row(output_buf, &result.name, result.category, result.num_calls)?
+                    }
+                }
+            }
+            write_ratio_rows(output_buf, section)?;
+        }
+        for result in &self.results {
+            if let Some(samples) = &result.samples {
+                write_histogram(output_buf, &result.name, samples)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_csv(&self, output_buf: &mut String) -> Result<(), BenchError> {
+        writeln!(
+            output_buf,
+            "name,category,status,total_cycles,total_calls,cycles_per_op,total_time_ms,min_cycles,max_cycles,\
+             sd_cycles,outliers,notes"
+        )
+        .map_err(|e| BenchError::WriteOutput("Write CSV header failed", e))?;
+        for result in &self.results {
+            let stats = match result.status {
+                BenchStatus::Measured => format!(
+                    "{},{},{},{},{},{},{}",
+                    csv_number(total_cycles(&result.stats)),
+                    result.num_calls,
+                    csv_number(result.stats.mean),
+                    csv_number(result.total_time_ms),
+                    csv_number(result.stats.min),
+                    csv_number(result.stats.max),
+                    csv_number(result.stats.std_dev),
+                ),
+                BenchStatus::DryRun => format!(",{},,,,,", result.num_calls),
+                BenchStatus::Skipped | BenchStatus::Failed => ",,,,,,".to_string(),
+            };
+            writeln!(
+                output_buf,
+                "{},{},{},{},{},{}",
+                csv_field(&result.name),
+                csv_field(result.category),
+                result.status.as_str(),
+                stats,
+                result.outliers,
+                csv_field(&result.notes),
+            )
+            .map_err(|e| BenchError::WriteOutput("Write CSV row failed", e))?;
+        }
+        Ok(())
+    }
+
+    fn write_samples_csv(&self, output_buf: &mut String) -> Result<(), BenchError> {
+        writeln!(output_buf, "name,iteration,cycles")
+            .map_err(|e| BenchError::WriteOutput("Write CSV header failed", e))?;
+        for result in &self.results {
+            for (iteration, cycles) in result.samples.iter().flatten().enumerate() {
+                writeln!(output_buf, "{},{},{}", csv_field(&result.name), iteration, csv_number(*cycles))
+                    .map_err(|e| BenchError::WriteOutput("Write CSV row failed", e))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_json(&self, output_buf: &mut String) -> Result<(), BenchError> {
+        output_buf.push_str("{\"results\":[");
+        for (i, result) in self.results.iter().enumerate() {
+            let measured = result.status == BenchStatus::Measured;
+            let number = |value: f64| if measured { json_number(value) } else { "null".to_string() };
+            write!(
+                output_buf,
+                "{}{{\"name\":{},\"category\":{},\"status\":\"{}\",\"total_cycles\":{},\"total_calls\":{},\
+                 \"cycles_per_op\":{},\"total_time_ms\":{},\"min_cycles\":{},\"max_cycles\":{},\"sd_cycles\":{},\
+                 \"outliers\":{},\"notes\":{},\"samples\":{}}}",
+                if i == 0 { "" } else { "," },
+                json_string(&result.name),
+                json_string(result.category),
+                result.status.as_str(),
+                number(total_cycles(&result.stats)),
+                result.num_calls,
+                number(result.stats.mean),
+                number(result.total_time_ms),
+                number(result.stats.min),
+                number(result.stats.max),
+                number(result.stats.std_dev),
+                result.outliers,
+                json_string(&result.notes),
+                match &result.samples {
+                    Some(samples) => json_array(samples),
+                    None => "null".to_string(),
+                },
+            )
+            .map_err(|e| BenchError::WriteOutput("Write JSON result failed", e))?;
+        }
+        output_buf.push_str("],\"ratios\":[");
+        let ratios = self.sections().flat_map(section_ratios);
+        for (i, (ratio, value)) in ratios.enumerate() {
+            write!(
+                output_buf,
+                "{}{{\"name\":{},\"numerator\":{},\"denominator\":{},\"value\":{}}}",
+                if i == 0 { "" } else { "," },
+                json_string(ratio.name),
+                json_string(ratio.numerator),
+                json_string(ratio.denominator),
+                json_number(value),
+            )
+            .map_err(|e| BenchError::WriteOutput("Write JSON ratio failed", e))?;
+        }
+        output_buf.push_str("]}\n");
+        Ok(())
+    }
+
+    /// Splits the results into runs of consecutive benchmarks of the same category.
+    fn sections(&self) -> impl Iterator<Item = &[BenchResult]> {
+        self.results.chunk_by(|a, b| a.category == b.category)
+    }
+}
+
+/// Returns every ratio in `BENCH_RATIOS` whose benchmarks were both measured in `section`, with its value.
+fn section_ratios(section: &[BenchResult]) -> impl Iterator<Item = (&'static BenchRatio, f64)> + '_ {
+    let mean_of = |name: &str| {
+        section
+            .iter()
+            .find(|result| result.status == BenchStatus::Measured && result.name == name)
+            .map(|result| result.stats.mean)
+    };
+    BENCH_RATIOS.iter().filter_map(move |ratio| {
+        let (Some(numerator), Some(denominator)) = (mean_of(ratio.numerator), mean_of(ratio.denominator)) else {
+            return None;
+        };
+        Some((ratio, numerator / denominator))
+    })
+}
+
+/// Returns the sum of the cycle counts of the measured iterations.
+fn total_cycles(stats: &Stats<f64>) -> f64 {
+    stats.mean * stats.count as f64
+}
+
+/// Formats a number for a CSV field, leaving it empty if it is not finite.
+fn csv_number(value: f64) -> String {
+    if value.is_finite() { value.to_string() } else { String::new() }
+}
+
+/// Quotes a CSV field if it contains a separator, quote, or line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) { format!("\"{}\"", s.replace('"', "\"\"")) } else { s.to_string() }
+}
+
+/// Formats a number as a JSON value. JSON has no representation for infinity or NaN, so those become `null`.
+fn json_number(value: f64) -> String {
+    if value.is_finite() { value.to_string() } else { "null".to_string() }
+}
+
+/// Formats numbers as a JSON array.
+fn json_array(values: &[f64]) -> String {
+    let items: Vec<String> = values.iter().map(|value| json_number(*value)).collect();
+    format!("[{}]", items.join(","))
+}
+
+/// Formats a string as a quoted JSON string.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Writes a markdown table listing the category, minimum UEFI version, and description of every benchmark.
+pub fn write_metadata_table(output_buf: &mut String) -> Result<(), BenchError> {
+    writeln!(output_buf, "| {:<40} | {:<10} | {:<8} | Description |", "Name", "Category", "Min UEFI")
+        .map_err(|e| BenchError::WriteOutput("Write metadata header failed", e))?;
+    writeln!(output_buf, "| {:-<40} | {:-<10} | {:-<8} | ----------- |", "-", "-", "-")
+        .map_err(|e| BenchError::WriteOutput("Write metadata header failed", e))?;
+    for (bf, _) in BENCH_FNS {
+        let (major, minor) = bf.min_uefi_version;
+        writeln!(
+            output_buf,
+            "| {:<40} | {:<10} | {:<8} | {} |",
+            bf.name,
+            bf.category,
+            format!("{}.{}", major, minor),
+            bf.description
+        )
+        .map_err(|e| BenchError::WriteOutput("Write metadata row failed", e))?;
+    }
+    writeln!(output_buf).map_err(|e| BenchError::WriteOutput("Write metadata table failed", e))?;
+    Ok(())
+}
+
+// Writes the header rows for the fixed-width results markdown table.
+pub fn write_headers(output_buf: &mut String) -> Result<(), BenchError> {
+    // Column headers.
+    writeln!(
+        output_buf,
+        "| {:<32} | {:>14} | {:>12} | {:>15} | {:>15} | {:>12} | {:>12} | {:>12} | {:>10} | {:<40} |",
+        "Name",
+        "Total cycles",
+        "Total calls",
+        "Cycles/op",
+        "Total time (ms)",
+        "Min cycles",
+        "Max cycles",
+        "SD [cycles]",
+        "Outliers",
+        "Notes"
+    )
+    .map_err(|e| BenchError::WriteOutput("Write table header failed", e))?;
+    // Column separators.
+    writeln!(
+        output_buf,
+        "| {:-<32} | {:-<14} | {:-<12} | {:-<15} | {:-<15} | {:-<12} | {:-<12} | {:-<12} | {:-<10} | {:-<40} |",
+        "-", "-", "-", "-", "-", "-", "-", "-", "-", "-"
+    )
+    .map_err(|e| BenchError::WriteOutput("Write table header failed", e))?;
+    Ok(())
+}
+
+pub fn write_result_row(
+    output_buf: &mut String,
+    bench_name: &str,
+    stats: Stats<f64>,
+    total_time_ms: f64,
+    num_calls: usize,
+    outliers: usize,
+    notes: &str,
+) -> Result<(), BenchError> {
+    writeln!(
+        output_buf,
+        "| {:<32} | {:>14} | {:>12} | {:>15} | {:>15.3} | {:>12} | {:>12} | {:>12.2} | {:>10} | {:<40} |",
+        bench_name,
+        stats.count, // Format as usize for better readability. Partial cycles don't really matter.
+        num_calls,
+        stats.mean,
+        total_time_ms,
+        stats.min,
+        stats.max,
+        stats.std_dev as usize, // Format as usize for better readability. Partial cycles don't really matter.
+        outliers,
+        notes,
+    )
+    .map_err(|e| BenchError::WriteOutput("Write table header failed", e))?;
+    Ok(())
+}
+
+/// Writes a results row for a benchmark that is listed but not run, with `(dry run)` in the cycle columns.
+pub fn write_dry_run_row(
+    output_buf: &mut String,
+    bench_name: &str,
+    category: &str,
+    num_calls: usize,
+) -> Result<(), BenchError> {
+    const DRY_RUN: &str = "(dry run)";
+    writeln!(
+        output_buf,
+        "| {:<32} | {:>14} | {:>12} | {:>15} | {:>15} | {:>12} | {:>12} | {:>12} | {:>10} | {:<40} |",
+        bench_name, DRY_RUN, num_calls, DRY_RUN, DRY_RUN, DRY_RUN, DRY_RUN, DRY_RUN, DRY_RUN, category,
+    )
+    .map_err(|e| BenchError::WriteOutput("Write dry run row failed", e))?;
+    Ok(())
+}
+
+/// Writes a row for every ratio in `BENCH_RATIOS` whose benchmarks were both measured in `section`.
+/// The ratio goes in the Cycles/op column and the benchmarks it compares in the Notes column.
+fn write_ratio_rows(output_buf: &mut String, section: &[BenchResult]) -> Result<(), BenchError> {
+    for (ratio, value) in section_ratios(section) {
+        writeln!(
+            output_buf,
+            "| {:<32} | {:>14} | {:>12} | {:>15.2} | {:>15} | {:>12} | {:>12} | {:>12} | {:>10} | {:<40} |",
+            ratio.name,
+            "",
+            "",
+            value,
+            "",
+            "",
+            "",
+            "",
+            "",
+            format!("{} / {}", ratio.numerator, ratio.denominator),
+        )
+        .map_err(|e| BenchError::WriteOutput("Write ratio row failed", e))?;
+    }
+    Ok(())
+}
+
+/// Writes a fixed-width histogram of the per-iteration cycle counts of a benchmark.
+pub fn write_histogram(output_buf: &mut String, bench_name: &str, samples: &[f64]) -> Result<(), BenchError> {
+    const BINS: usize = 10;
+    const BAR_WIDTH: usize = 40;
+
+    if samples.is_empty() {
+        return Ok(());
+    }
+
+    let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let bin_width = (max - min) / BINS as f64;
+
+    let mut counts = [0usize; BINS];
+    for &sample in samples {
+        let bin = if bin_width > 0.0 { ((sample - min) / bin_width) as usize } else { 0 };
+        counts[bin.min(BINS - 1)] += 1;
+    }
+    let peak = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    writeln!(output_buf, "\n{} (cycles/op distribution)\n", bench_name)
+        .map_err(|e| BenchError::WriteOutput("Write histogram header failed", e))?;
+    writeln!(output_buf, "| {:>14} | {:>14} | {:>12} | {:<BAR_WIDTH$} |", "From cycles", "To cycles", "Calls", "")
+        .map_err(|e| BenchError::WriteOutput("Write histogram header failed", e))?;
+    writeln!(output_buf, "| {:-<14} | {:-<14} | {:-<12} | {:-<BAR_WIDTH$} |", "-", "-", "-", "-")
+        .map_err(|e| BenchError::WriteOutput("Write histogram header failed", e))?;
+    for (i, count) in counts.into_iter().enumerate() {
+        let from = min + bin_width * i as f64;
+        let bar = "#".repeat(count * BAR_WIDTH / peak);
+        writeln!(
+            output_buf,
+            "| {:>14} | {:>14} | {:>12} | {:<BAR_WIDTH$} |",
+            from as usize, // Format as usize for better readability. Partial cycles don't really matter.
+            (from + bin_width) as usize,
+            count,
+            bar
+        )
+        .map_err(|e| BenchError::WriteOutput("Write histogram row failed", e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a measured result with iterations of 100 and 300 cycles.
+    fn measured(name: &str, category: &'static str) -> BenchResult {
+        let mut stats = Stats::new();
+        stats.update(100.0);
+        stats.update(300.0);
+        stats.std_dev = 50.0;
+        BenchResult {
+            stats,
+            total_time_ms: 0.5,
+            num_calls: 2,
+            ..BenchResult::unmeasured(name, category, BenchStatus::Measured)
+        }
+    }
+
+    /// Creates a result of every status.
+    fn all_statuses() -> Vec<BenchResult> {
+        vec![
+            measured("allocate_pool", "Memory"),
+            BenchResult {
+                notes: "Requires UEFI 2.0".to_string(),
+                ..BenchResult::unmeasured("free_pool", "Memory", BenchStatus::Skipped)
+            },
+            BenchResult::unmeasured("allocate_pages", "Memory", BenchStatus::Failed),
+            BenchResult { num_calls: 100, ..BenchResult::unmeasured("free_pages", "Memory", BenchStatus::DryRun) },
+        ]
+    }
+
+    /// Returns the trimmed cells of the markdown results table row of the benchmark `name`.
+    fn markdown_cells(markdown: &str, name: &str) -> Vec<String> {
+        let prefix = format!("| {:<32} |", name);
+        let row = markdown.lines().find(|line| line.starts_with(&prefix)).unwrap();
+        row.trim_matches('|').split('|').map(|cell| cell.trim().to_string()).collect()
+    }
+
+    #[test]
+    fn markdown_has_a_row_per_status() {
+        let markdown = PerfReport::new(all_statuses()).as_markdown();
+        assert_eq!(
+            markdown_cells(&markdown, "allocate_pool"),
+            ["allocate_pool", "2", "2", "200", "0.500", "100", "300", "50", "0", ""]
+        );
+        // Results that were not measured have no statistics, so only the name and notes matter.
+        let cells = markdown_cells(&markdown, "free_pool (Skipped)");
+        assert_eq!(cells.last().unwrap(), "Requires UEFI 2.0");
+        let cells = markdown_cells(&markdown, "allocate_pages (Failed)");
+        assert_eq!(cells.last().unwrap(), "");
+        assert_eq!(
+            markdown_cells(&markdown, "free_pages"),
+            [
+                "free_pages",
+                "(dry run)",
+                "100",
+                "(dry run)",
+                "(dry run)",
+                "(dry run)",
+                "(dry run)",
+                "(dry run)",
+                "(dry run)",
+                "Memory"
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_leaves_statistics_of_unmeasured_results_empty() {
+        let csv = PerfReport::new(all_statuses()).as_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "name,category,status,total_cycles,total_calls,cycles_per_op,total_time_ms,min_cycles,max_cycles,\
+                 sd_cycles,outliers,notes",
+                "allocate_pool,Memory,measured,400,2,200,0.5,100,300,50,0,",
+                "free_pool,Memory,skipped,,,,,,,,0,Requires UEFI 2.0",
+                "allocate_pages,Memory,failed,,,,,,,,0,",
+                "free_pages,Memory,dry_run,,100,,,,,,0,",
+            ]
+        );
+    }
+
+    #[test]
+    fn json_has_null_statistics_for_unmeasured_results() {
+        let json = PerfReport::new(all_statuses()).as_json();
+        assert_eq!(
+            json,
+            "{\"results\":[\
+             {\"name\":\"allocate_pool\",\"category\":\"Memory\",\"status\":\"measured\",\"total_cycles\":400,\
+             \"total_calls\":2,\"cycles_per_op\":200,\"total_time_ms\":0.5,\"min_cycles\":100,\"max_cycles\":300,\
+             \"sd_cycles\":50,\"outliers\":0,\"notes\":\"\",\"samples\":null},\
+             {\"name\":\"free_pool\",\"category\":\"Memory\",\"status\":\"skipped\",\"total_cycles\":null,\
+             \"total_calls\":0,\"cycles_per_op\":null,\"total_time_ms\":null,\"min_cycles\":null,\"max_cycles\":null,\
+             \"sd_cycles\":null,\"outliers\":0,\"notes\":\"Requires UEFI 2.0\",\"samples\":null},\
+             {\"name\":\"allocate_pages\",\"category\":\"Memory\",\"status\":\"failed\",\"total_cycles\":null,\
+             \"total_calls\":0,\"cycles_per_op\":null,\"total_time_ms\":null,\"min_cycles\":null,\"max_cycles\":null,\
+             \"sd_cycles\":null,\"outliers\":0,\"notes\":\"\",\"samples\":null},\
+             {\"name\":\"free_pages\",\"category\":\"Memory\",\"status\":\"dry_run\",\"total_cycles\":null,\
+             \"total_calls\":100,\"cycles_per_op\":null,\"total_time_ms\":null,\"min_cycles\":null,\"max_cycles\":null,\
+             \"sd_cycles\":null,\"outliers\":0,\"notes\":\"\",\"samples\":null}\
+             ],\"ratios\":[]}\n"
+        );
+    }
+
+    #[test]
+    fn json_includes_recorded_samples() {
+        let result = BenchResult { samples: Some(vec![100.0, 300.0]), ..measured("allocate_pool", "Memory") };
+        let json = PerfReport::new(vec![result]).as_json();
+        assert!(json.contains("\"samples\":[100,300]"));
+    }
+
+    #[test]
+    fn csv_field_quotes_special_characters() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("cr\r"), "\"cr\r\"");
+    }
+
+    #[test]
+    fn json_string_escapes_special_characters() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(json_string("back\\slash"), "\"back\\\\slash\"");
+        assert_eq!(json_string("a\nb\rc\td"), "\"a\\nb\\rc\\td\"");
+        assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+        assert_eq!(json_string("µs"), "\"µs\"");
+    }
+
+    #[test]
+    fn numbers_that_are_not_finite_are_left_out() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(json_number(value), "null");
+            assert_eq!(csv_number(value), "");
+        }
+        assert_eq!(json_number(1.5), "1.5");
+        assert_eq!(csv_number(1.5), "1.5");
+    }
+
+    #[test]
+    fn ratio_is_reported_when_both_benchmarks_are_in_one_section() {
+        let mut event_ex = measured("create_event_ex_null_group", "Event");
+        event_ex.stats.mean = 300.0;
+        let report = PerfReport::new(vec![measured("create_event", "Event"), event_ex]);
+        assert!(report.as_markdown().contains("| create_event_ex overhead ratio   |"));
+        assert!(report.as_json().contains(
+            "\"ratios\":[{\"name\":\"create_event_ex overhead ratio\",\"numerator\":\"create_event_ex_null_group\",\
+             \"denominator\":\"create_event\",\"value\":1.5}]"
+        ));
+    }
+
+    #[test]
+    fn ratio_is_not_reported_across_sections() {
+        let report = PerfReport::new(vec![
+            measured("create_event", "Event"),
+            measured("allocate_pool", "Memory"),
+            measured("create_event_ex_null_group", "Event"),
+        ]);
+        assert!(!report.as_markdown().contains("create_event_ex overhead ratio"));
+        assert!(report.as_json().ends_with("\"ratios\":[]}\n"));
+    }
+
+    #[test]
+    fn ratio_is_not_reported_for_an_unmeasured_benchmark() {
+        let report = PerfReport::new(vec![
+            measured("create_event", "Event"),
+            BenchResult::unmeasured("create_event_ex_null_group", "Event", BenchStatus::Skipped),
+        ]);
+        assert!(!report.as_markdown().contains("create_event_ex overhead ratio"));
+        assert!(report.as_json().ends_with("\"ratios\":[]}\n"));
+    }
+}